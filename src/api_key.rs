@@ -0,0 +1,127 @@
+pub mod api_key {
+    use crate::Passgen;
+    use crate::gen_engine::gen_engine::new_rng;
+    use rand::Rng;
+
+    // Base62 alphabet of the API keys.
+    pub const BASE62_ALPHABET: &str =
+        "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+    // Length of a 32-bit checksum in base62: 62^6 > 2^32.
+    pub const CHECKSUM_LENGTH: usize = 6;
+
+    /// Checksum algorithms of the API keys.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum ChecksumAlgorithm {
+        /// CRC-32 of ISO-HDLC, like in zip and PNG.
+        Crc32,
+        /// CRC-32C of Castagnoli, like in iSCSI and ext4.
+        Crc32c,
+    }
+
+    impl ChecksumAlgorithm {
+        /// Compute the checksum of the data.
+        pub fn checksum(&self, data: &[u8]) -> u32 {
+            // Reversed polynomials of the algorithms
+            let polynomial: u32 = match self {
+                ChecksumAlgorithm::Crc32 => 0xEDB8_8320,
+                ChecksumAlgorithm::Crc32c => 0x82F6_3B78,
+            };
+            let mut crc = !0u32;
+            for &byte in data {
+                crc ^= byte as u32;
+                for _ in 0..8 {
+                    crc = if crc & 1 != 0 {
+                        (crc >> 1) ^ polynomial
+                    } else {
+                        crc >> 1
+                    };
+                }
+            }
+            !crc
+        }
+    }
+
+    /// Format of the API keys: `<prefix><base62 body><base62 checksum of the body>`,
+    /// like `acme_live_` followed by 30 random and 6 checksum characters.
+    ///
+    /// Prefixes make leaked keys detectable by secret scanners
+    /// and checksums let typos be rejected without a database lookup.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct ApiKeyFormat {
+        /// Prefix of the keys, like `acme_live_`.
+        pub prefix: String,
+        /// Number of random base62 characters.
+        pub body_length: u32,
+        /// Checksum of the body appended as 6 base62 characters, `None` for keys without it.
+        pub checksum: Option<ChecksumAlgorithm>,
+    }
+
+    impl ApiKeyFormat {
+        /// Format with the prefix, 30 random characters and the CRC-32 checksum.
+        pub fn new(prefix: &str) -> ApiKeyFormat {
+            ApiKeyFormat {
+                prefix: prefix.to_string(),
+                body_length: 30,
+                checksum: Some(ChecksumAlgorithm::Crc32),
+            }
+        }
+
+        /// Get the entropy in bits of the keys of the format.
+        pub fn entropy_bits(&self) -> f64 {
+            self.body_length as f64 * (BASE62_ALPHABET.len() as f64).log2()
+        }
+
+        // Checksum of the body encoded in base62 with the leading zeros.
+        fn encoded_checksum(&self, body: &str) -> Option<String> {
+            let mut value = self.checksum?.checksum(body.as_bytes()) as u64;
+            let alphabet = BASE62_ALPHABET.as_bytes();
+            let mut digits = vec![alphabet[0]; CHECKSUM_LENGTH];
+            for digit in digits.iter_mut().rev() {
+                *digit = alphabet[(value % 62) as usize];
+                value /= 62;
+            }
+            Some(String::from_utf8_lossy(&digits).into_owned())
+        }
+    }
+
+    impl Passgen {
+        /// Generate an API key of the format.
+        /// The key is automatically stored in the `password` field.
+        pub fn generate_api_key(&mut self, format: &ApiKeyFormat) -> String {
+            let mut rng = new_rng();
+            let alphabet: Vec<char> = BASE62_ALPHABET.chars().collect();
+            let body: String = (0..format.body_length)
+                .map(|_| alphabet[rng.random_range(0..alphabet.len())])
+                .collect();
+
+            let key = format!(
+                "{}{}{}",
+                format.prefix,
+                body,
+                format.encoded_checksum(&body).unwrap_or_default()
+            );
+            self.password = key.clone();
+            key
+        }
+    }
+
+    /// Check the prefix, the length, the characters and the checksum of the API key offline.
+    pub fn verify_api_key(key: &str, format: &ApiKeyFormat) -> bool {
+        let Some(rest) = key.strip_prefix(format.prefix.as_str()) else {
+            return false;
+        };
+        let checksum_length = if format.checksum.is_some() {
+            CHECKSUM_LENGTH
+        } else {
+            0
+        };
+        if rest.len() != format.body_length as usize + checksum_length
+            || !rest.chars().all(|ch| ch.is_ascii_alphanumeric())
+        {
+            return false;
+        }
+
+        let (body, checksum) = rest.split_at(format.body_length as usize);
+        format.encoded_checksum(body).unwrap_or_default() == checksum
+    }
+}
@@ -0,0 +1,215 @@
+pub mod check_digit {
+    use crate::Passgen;
+    use crate::gen_engine::gen_engine::{NUM_CHARSET, new_rng};
+    use rand::Rng;
+
+    // Alphabet of ISO 7064 MOD 37,36: numbers and capital letters.
+    pub const ISO7064_MOD37_36_ALPHABET: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    // Quasigroup of order 10 of the Damm algorithm.
+    const DAMM_TABLE: [[u8; 10]; 10] = [
+        [0, 3, 1, 7, 5, 9, 8, 6, 4, 2],
+        [7, 0, 9, 2, 1, 5, 4, 8, 6, 3],
+        [4, 2, 0, 6, 8, 7, 1, 3, 5, 9],
+        [1, 7, 5, 0, 9, 8, 3, 4, 2, 6],
+        [6, 1, 2, 3, 0, 4, 5, 9, 7, 8],
+        [3, 6, 7, 4, 2, 0, 9, 5, 8, 1],
+        [5, 8, 6, 9, 7, 2, 0, 1, 3, 4],
+        [8, 9, 4, 5, 3, 6, 2, 0, 1, 7],
+        [9, 4, 3, 8, 6, 1, 7, 2, 0, 5],
+        [2, 5, 8, 1, 4, 3, 6, 7, 9, 0],
+    ];
+    // Multiplication of the dihedral group D5 of the Verhoeff algorithm.
+    const VERHOEFF_MULTIPLICATION: [[u8; 10]; 10] = [
+        [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+        [1, 2, 3, 4, 0, 6, 7, 8, 9, 5],
+        [2, 3, 4, 0, 1, 7, 8, 9, 5, 6],
+        [3, 4, 0, 1, 2, 8, 9, 5, 6, 7],
+        [4, 0, 1, 2, 3, 9, 5, 6, 7, 8],
+        [5, 9, 8, 7, 6, 0, 4, 3, 2, 1],
+        [6, 5, 9, 8, 7, 1, 0, 4, 3, 2],
+        [7, 6, 5, 9, 8, 2, 1, 0, 4, 3],
+        [8, 7, 6, 5, 9, 3, 2, 1, 0, 4],
+        [9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+    ];
+    // Permutations of the digits by their positions of the Verhoeff algorithm.
+    const VERHOEFF_PERMUTATION: [[u8; 10]; 8] = [
+        [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+        [1, 5, 7, 6, 2, 8, 3, 0, 9, 4],
+        [5, 8, 0, 3, 7, 9, 6, 1, 4, 2],
+        [8, 9, 1, 6, 0, 4, 3, 5, 2, 7],
+        [9, 4, 5, 3, 1, 2, 6, 8, 7, 0],
+        [4, 2, 8, 6, 5, 7, 3, 9, 0, 1],
+        [2, 7, 9, 3, 8, 0, 6, 4, 1, 5],
+        [7, 0, 4, 6, 9, 1, 3, 2, 5, 8],
+    ];
+    // Inverses in the dihedral group D5 of the Verhoeff algorithm.
+    const VERHOEFF_INVERSE: [u8; 10] = [0, 4, 3, 2, 1, 5, 6, 7, 8, 9];
+
+    /// Algorithms of the trailing check character of the codes typed by hand.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum CheckAlgorithm {
+        /// Luhn algorithm of the numeric codes, like in the payment card numbers.
+        /// Detects all single-digit typos and most transpositions, except `09` and `90`.
+        Luhn,
+        /// Damm algorithm of the numeric codes.
+        /// Detects all single-digit typos and adjacent transpositions.
+        Damm,
+        /// Verhoeff algorithm of the numeric codes.
+        /// Detects all single-digit typos and adjacent transpositions.
+        Verhoeff,
+        /// ISO 7064 MOD 37,36 of the alphanumeric codes, case insensitive.
+        /// Detects all single-character typos and most adjacent transpositions.
+        Iso7064Mod37_36,
+        /// ISO 7064 hybrid system MOD (N+1),N of the codes of a custom alphabet of N distinct characters.
+        /// Detects all single-character typos and most adjacent transpositions.
+        ///
+        /// ⚠️The size of the alphabet must be even, like 10, 26 or 36 of the ISO 7064 systems,
+        /// otherwise not all typos are detected. Alphabets which are empty, of an odd size
+        /// or with repeated characters have no check characters.
+        Iso7064Hybrid(&'static str),
+    }
+
+    impl CheckAlgorithm {
+        /// Characters of the codes and the check characters.
+        pub fn alphabet(&self) -> &'static str {
+            match self {
+                CheckAlgorithm::Luhn | CheckAlgorithm::Damm | CheckAlgorithm::Verhoeff => {
+                    NUM_CHARSET
+                }
+                CheckAlgorithm::Iso7064Mod37_36 => ISO7064_MOD37_36_ALPHABET,
+                CheckAlgorithm::Iso7064Hybrid(alphabet) => alphabet,
+            }
+        }
+
+        /// Compute the check character of the payload.
+        /// Returns `None` if the payload contains characters not in the alphabet
+        /// or the alphabet of [CheckAlgorithm::Iso7064Hybrid] is not supported.
+        pub fn check_char(&self, payload: &str) -> Option<char> {
+            if !self.is_alphabet_supported() {
+                return None;
+            }
+            let values = self.values(payload)?;
+            let check = match self {
+                CheckAlgorithm::Luhn => luhn(&values),
+                CheckAlgorithm::Damm => values
+                    .iter()
+                    .fold(0, |interim, &digit| DAMM_TABLE[interim][digit] as usize),
+                CheckAlgorithm::Verhoeff => verhoeff(&values),
+                CheckAlgorithm::Iso7064Mod37_36 | CheckAlgorithm::Iso7064Hybrid(_) => {
+                    iso7064_hybrid(&values, self.alphabet().chars().count())
+                }
+            };
+            self.alphabet().chars().nth(check)
+        }
+
+        /// Check if the trailing check character of the code matches the rest of it.
+        pub fn validate(&self, code: &str) -> bool {
+            let mut payload = code.to_string();
+            let Some(check) = payload.pop() else {
+                return false;
+            };
+            // The check character is compared by its value, so the case doesn't matter
+            // where the algorithm is case insensitive
+            match (self.values(&check.to_string()), self.check_char(&payload)) {
+                (Some(value), Some(expected)) => {
+                    self.alphabet().chars().nth(value[0]) == Some(expected)
+                }
+                _ => false,
+            }
+        }
+
+        // Only the alphabets of an even size without repeated characters make the doubling
+        // of the hybrid system modulo N+1 one-to-one, so every typo changes the check character.
+        fn is_alphabet_supported(&self) -> bool {
+            let alphabet: Vec<char> = self.alphabet().chars().collect();
+            let mut distinct = alphabet.clone();
+            distinct.sort_unstable();
+            distinct.dedup();
+            !alphabet.is_empty()
+                && alphabet.len().is_multiple_of(2)
+                && distinct.len() == alphabet.len()
+        }
+
+        // Values of the characters of the payload in the alphabet.
+        fn values(&self, payload: &str) -> Option<Vec<usize>> {
+            payload
+                .chars()
+                .map(|ch| {
+                    let ch = match self {
+                        CheckAlgorithm::Iso7064Mod37_36 => ch.to_ascii_uppercase(),
+                        _ => ch,
+                    };
+                    self.alphabet().chars().position(|letter| letter == ch)
+                })
+                .collect()
+        }
+    }
+
+    impl Passgen {
+        /// Generate a code of the length with a trailing check character of the algorithm,
+        /// the other characters are random characters of the alphabet of the algorithm.
+        /// Argument "length" includes the check character and will not be less than 2.
+        /// The code is automatically stored in the `password` field.
+        /// Returns an empty string if the alphabet of the algorithm is not supported.
+        pub fn generate_check_code(&mut self, length: u32, algorithm: CheckAlgorithm) -> String {
+            if !algorithm.is_alphabet_supported() {
+                self.password.clear();
+                return "".to_string();
+            }
+            let alphabet: Vec<char> = algorithm.alphabet().chars().collect();
+            let mut rng = new_rng();
+            let mut code: String = (1..length.max(2))
+                .map(|_| alphabet[rng.random_range(0..alphabet.len())])
+                .collect();
+            if let Some(check) = algorithm.check_char(&code) {
+                code.push(check);
+            }
+            self.password = code.clone();
+            code
+        }
+    }
+
+    // Every second digit counting from the check digit is doubled, starting with its neighbour.
+    fn luhn(digits: &[usize]) -> usize {
+        let sum: usize = digits
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, &digit)| {
+                if i % 2 == 0 {
+                    let doubled = digit * 2;
+                    if doubled > 9 { doubled - 9 } else { doubled }
+                } else {
+                    digit
+                }
+            })
+            .sum();
+        (10 - sum % 10) % 10
+    }
+
+    // Positions are counted from the check digit.
+    fn verhoeff(digits: &[usize]) -> usize {
+        let checksum = digits
+            .iter()
+            .rev()
+            .enumerate()
+            .fold(0, |checksum, (i, &digit)| {
+                let permuted = VERHOEFF_PERMUTATION[(i + 1) % 8][digit] as usize;
+                VERHOEFF_MULTIPLICATION[checksum][permuted] as usize
+            });
+        VERHOEFF_INVERSE[checksum] as usize
+    }
+
+    // Hybrid system of ISO 7064 with the modulus of the alphabet size and the one more.
+    fn iso7064_hybrid(values: &[usize], modulus: usize) -> usize {
+        let product = values.iter().fold(modulus, |product, &value| {
+            let sum = match (product + value) % modulus {
+                0 => modulus,
+                sum => sum,
+            };
+            (sum * 2) % (modulus + 1)
+        });
+        (modulus + 1 - product) % modulus
+    }
+}
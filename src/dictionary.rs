@@ -0,0 +1,78 @@
+pub mod dictionary {
+    use crate::lang::lang::{LANGUAGES, Language};
+
+    /// Which per-language dictionaries are consulted by the strength scoring.
+    ///
+    /// Dictionaries are optional and enabled by the crate features `dict-<language>`,
+    /// for example `dict-german`, or all at once by `dict-all`.
+    ///
+    /// ⚠️Bundled dictionaries are samples of a few dozen of the most common words
+    /// of every language, not full frequency lists.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum DictionaryScope {
+        /// Only the dictionary of the configured `language`.
+        Language,
+        /// Dictionaries of all enabled languages.
+        AllEnabled,
+        /// Per-language dictionaries are not consulted.
+        Disabled,
+    }
+
+    /// Get the raw dictionary of the language if it is enabled.
+    /// Contains one word per line, lines starting with `#` are comments.
+    pub fn dictionary(language: Language) -> Option<&'static str> {
+        #[allow(unreachable_patterns)]
+        match language {
+            #[cfg(feature = "dict-english")]
+            Language::English => Some(include_str!("dict/english.txt")),
+            #[cfg(feature = "dict-chinese")]
+            Language::Chinese => Some(include_str!("dict/chinese.txt")),
+            #[cfg(feature = "dict-spanish")]
+            Language::Spanish => Some(include_str!("dict/spanish.txt")),
+            #[cfg(feature = "dict-hindi")]
+            Language::Hindi => Some(include_str!("dict/hindi.txt")),
+            #[cfg(feature = "dict-arabic")]
+            Language::Arabic => Some(include_str!("dict/arabic.txt")),
+            #[cfg(feature = "dict-portuguese")]
+            Language::Portuguese => Some(include_str!("dict/portuguese.txt")),
+            #[cfg(feature = "dict-bengali")]
+            Language::Bengali => Some(include_str!("dict/bengali.txt")),
+            #[cfg(feature = "dict-russian")]
+            Language::Russian => Some(include_str!("dict/russian.txt")),
+            #[cfg(feature = "dict-japanese")]
+            Language::Japanese => Some(include_str!("dict/japanese.txt")),
+            #[cfg(feature = "dict-punjabi")]
+            Language::Punjabi => Some(include_str!("dict/punjabi.txt")),
+            #[cfg(feature = "dict-german")]
+            Language::German => Some(include_str!("dict/german.txt")),
+            #[cfg(feature = "dict-korean")]
+            Language::Korean => Some(include_str!("dict/korean.txt")),
+            #[cfg(feature = "dict-french")]
+            Language::French => Some(include_str!("dict/french.txt")),
+            #[cfg(feature = "dict-turkish")]
+            Language::Turkish => Some(include_str!("dict/turkish.txt")),
+            #[cfg(feature = "dict-italian")]
+            Language::Italian => Some(include_str!("dict/italian.txt")),
+            _ => None,
+        }
+    }
+
+    /// Get common passwords and words of the language,
+    /// including romanized or transliterated forms where relevant.
+    /// Empty if the dictionary of the language is not enabled.
+    pub fn words(language: Language) -> impl Iterator<Item = &'static str> {
+        dictionary(language)
+            .unwrap_or_default()
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    }
+
+    /// Get languages with enabled dictionaries.
+    pub fn enabled_languages() -> Vec<Language> {
+        LANGUAGES
+            .into_iter()
+            .filter(|&language| dictionary(language).is_some())
+            .collect()
+    }
+}
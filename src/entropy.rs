@@ -0,0 +1,308 @@
+pub mod entropy {
+    use crate::Passgen;
+    use crate::gen_engine::gen_engine::{GenerationError, LengthDistribution};
+    use std::collections::{HashMap, HashSet};
+
+    /// Generated password with its metadata.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct GeneratedPassword {
+        /// The generated password.
+        pub password: String,
+        /// Length of the password in characters without the separators of the groups.
+        pub length: u32,
+        /// Exact entropy of the generator configuration the password was drawn from,
+        /// including the entropy of the random length for `generate_range_with_metadata`.
+        /// `None` if it can't be computed exactly under the rules.
+        pub entropy_bits: Option<f64>,
+    }
+
+    impl Passgen {
+        /// Get the entropy in bits of the results of `generate(length)` for the current ruleset.
+        ///
+        /// It is computed exactly from the characters `generate` samples every position from,
+        /// including the reduced first and last positions of the *"Strong & usability"* rule,
+        /// the rejection of results without a character of every required type
+        /// repeated characters of a custom charset and the position constraints.
+        /// Results of `enab_unique_chars` are counted as drawn without replacement.
+        ///
+        /// Returns 0 if nothing can be generated: the ruleset is clean, a position
+        /// has no characters left or no result satisfies the rules.
+        /// Returns `None` if the entropy can't be computed exactly: under `max_consecutive_chars`
+        /// and `enab_no_sequences`, or `enab_unique_chars` with positions drawn
+        /// from different characters, like under the *"Strong & usability"* rule.
+        pub fn entropy_bits(&self, length: u32) -> Option<f64> {
+            if self.is_ruleset_clean() {
+                return Some(0.0);
+            }
+            // The same minimal length as in `generate`
+            let pools = self.grouped_position_pools(length.max(4));
+            if pools.iter().any(|(pool, _)| pool.is_empty()) {
+                return Some(0.0);
+            }
+
+            // Characters filtered by the ones before them are not drawn uniformly
+            if self.enab_no_sequences || (self.max_consecutive_chars > 0 && !self.enab_unique_chars)
+            {
+                return None;
+            }
+
+            if self.enab_unique_chars {
+                // Results of a custom charset are not checked for the required charsets
+                let required = if self.custom_charset.is_empty() {
+                    self.required_charsets()
+                } else {
+                    Vec::new()
+                };
+                unique_bits(&pools, &required)
+            } else if !self.custom_charset.is_empty() {
+                // No rejection, but a custom charset can contain repeated characters
+                Some(
+                    pools
+                        .iter()
+                        .map(|(pool, count)| shannon_bits(pool) * *count as f64)
+                        .sum(),
+                )
+            } else {
+                Some(accepted_bits(&pools, &self.required_charsets()))
+            }
+        }
+
+        /// Get the smallest length of the results of the current ruleset
+        /// which have at least the requested entropy in bits.
+        /// Returns `None` if the entropy can't be reached or computed, for example
+        /// for the clean ruleset, or if the requested entropy is not a positive number.
+        pub fn length_for_entropy(&self, bits: f64) -> Option<u32> {
+            if !bits.is_finite() || bits <= 0.0 {
+                return None;
+            }
+            // Entropy grows with the length, so the upper bound is found by doubling
+            // and then the smallest length is found by the binary search.
+            // Results without repeated characters are not longer than the characters
+            // to draw from, so the search stops there.
+            let limit = if self.enab_unique_chars {
+                self.grouped_position_pools(4)
+                    .iter()
+                    .map(|(pool, _)| pool.len() as u32)
+                    .max()
+                    .unwrap_or(0)
+                    .max(4)
+            } else {
+                u32::MAX
+            };
+            let mut low = 4;
+            let mut high = 4;
+            while self.entropy_bits(high)? < bits {
+                let next = high.saturating_mul(2).min(limit);
+                if next == high || self.entropy_bits(next)? <= self.entropy_bits(high)? {
+                    return None;
+                }
+                low = high + 1;
+                high = next;
+            }
+            while low < high {
+                let middle = low + (high - low) / 2;
+                if self.entropy_bits(middle)? < bits {
+                    low = middle + 1;
+                } else {
+                    high = middle;
+                }
+            }
+            Some(high)
+        }
+
+        /// Generate result of the smallest length which reaches the requested entropy in bits
+        /// under the current ruleset or custom charset.
+        /// For example 128 bits need 22 characters of letters and numbers, but 39 of numbers only.
+        ///
+        /// Returns the error if the entropy can't be computed or reached,
+        /// or the result of that length can't be generated, like `try_generate`.
+        pub fn generate_with_entropy(&mut self, bits: f64) -> Result<String, GenerationError> {
+            if let Some(length) = self.length_for_entropy(bits) {
+                return self.try_generate(length);
+            }
+            self.password.clear();
+            if self.is_ruleset_clean() {
+                Err(GenerationError::CleanRuleset)
+            } else if self.entropy_bits(4).is_none() {
+                Err(GenerationError::EntropyUnknown)
+            } else {
+                Err(GenerationError::EntropyNotReached)
+            }
+        }
+
+        /// Get the entropy in bits of the results of `generate_range(min, max)`
+        /// for the current ruleset, including the entropy of the random length.
+        /// Returns `None` if the entropy of some length can't be computed, like `entropy_bits`.
+        pub fn entropy_bits_range(&self, min: u32, max: u32) -> Option<f64> {
+            if self.is_ruleset_clean() {
+                return Some(0.0);
+            }
+            // Results of different lengths are different, so the entropy is
+            // the entropy of the length plus the average entropy of the results
+            self.length_probabilities(min, max)
+                .iter()
+                .filter(|(_, probability)| *probability > 0.0)
+                .map(|&(length, probability)| {
+                    Some(probability * (self.entropy_bits(length)? - probability.log2()))
+                })
+                .sum()
+        }
+
+        /// Get the probability of every length of `generate_range(min, max)`.
+        pub(crate) fn length_probabilities(&self, min: u32, max: u32) -> Vec<(u32, f64)> {
+            // The same minimal length as in `generate`
+            let min = min.max(4);
+            let max = max.max(min);
+
+            // Unknown entropy of some length leaves nothing to weight the lengths by
+            let bits: Option<Vec<(u32, f64)>> = match self.length_distribution {
+                LengthDistribution::Uniform => None,
+                LengthDistribution::Keyspace => (min..=max)
+                    .map(|length| Some((length, self.entropy_bits(length)?)))
+                    .collect(),
+            };
+            match bits {
+                None => {
+                    let count = (max - min + 1) as f64;
+                    (min..=max).map(|length| (length, 1.0 / count)).collect()
+                }
+                Some(bits) => {
+                    // Weights are 2^bits, scaled by the largest one to avoid overflow
+                    let max_bits = bits.iter().map(|&(_, b)| b).fold(f64::MIN, f64::max);
+                    let total: f64 = bits.iter().map(|&(_, b)| (b - max_bits).exp2()).sum();
+                    bits.iter()
+                        .map(|&(length, b)| (length, (b - max_bits).exp2() / total))
+                        .collect()
+                }
+            }
+        }
+
+        /// Generate result like `generate_range(min, max)` and return it with the metadata.
+        pub fn generate_range_with_metadata(&mut self, min: u32, max: u32) -> GeneratedPassword {
+            let password = self.generate_range(min, max);
+            GeneratedPassword {
+                length: self.ungrouped_password().chars().count() as u32,
+                entropy_bits: self.entropy_bits_range(min, max),
+                password,
+            }
+        }
+
+        /// Generate result like `generate(length)` and return it with the metadata.
+        pub fn generate_with_metadata(&mut self, length: u32) -> GeneratedPassword {
+            let password = self.generate(length);
+            GeneratedPassword {
+                length: self.ungrouped_password().chars().count() as u32,
+                entropy_bits: self.entropy_bits(length),
+                password,
+            }
+        }
+    }
+
+    // Entropy of a single position drawn uniformly from the pool.
+    fn shannon_bits(pool: &[char]) -> f64 {
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        for &ch in pool {
+            *counts.entry(ch).or_default() += 1;
+        }
+        let total = pool.len() as f64;
+        counts
+            .values()
+            .map(|&count| {
+                let p = count as f64 / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    // Entropy of the results uniformly distributed over the strings drawn from the pools
+    // which contain at least one character of every required charset.
+    // Pools are given with the number of positions drawn from them.
+    fn accepted_bits(pools: &[(Vec<char>, u64)], required: &[&str]) -> f64 {
+        let all_bits: f64 = pools
+            .iter()
+            .map(|(pool, count)| (pool.len() as f64).log2() * *count as f64)
+            .sum();
+
+        // Share of the strings without the characters of the excluded charsets
+        let accepted_share = accepted_share(required, |excluded| {
+            pools
+                .iter()
+                .map(|(pool, count)| {
+                    let left = pool
+                        .iter()
+                        .filter(|&&ch| !excluded.iter().any(|charset| charset.contains(ch)))
+                        .count();
+                    (left as f64 / pool.len() as f64).powf(*count as f64)
+                })
+                .product()
+        });
+
+        if accepted_share > 0.0 {
+            all_bits + accepted_share.log2()
+        } else {
+            0.0
+        }
+    }
+
+    // Entropy of the results without repeated characters drawn from a single pool
+    // of distinct characters, which contain at least one character of every required charset.
+    // Every such string is equally likely, so the entropy is the logarithm of their number:
+    // the number of the arrangements of the pool minus the ones missing a required charset.
+    // Returns `None` if the positions are drawn from different pools or the pool repeats
+    // characters, and 0 if the pool is too small for the results.
+    fn unique_bits(pools: &[(Vec<char>, u64)], required: &[&str]) -> Option<f64> {
+        let [(pool, length)] = pools else {
+            return None;
+        };
+        let size = pool.len() as u64;
+        let distinct: HashSet<char> = pool.iter().copied().collect();
+        if distinct.len() as u64 != size {
+            return None;
+        }
+        if *length > size {
+            return Some(0.0);
+        }
+        let all_bits: f64 = (0..*length).map(|i| ((size - i) as f64).log2()).sum();
+
+        // Share of the arrangements without the characters of the excluded charsets
+        let accepted_share = accepted_share(required, |excluded| {
+            let left = pool
+                .iter()
+                .filter(|&&ch| !excluded.iter().any(|charset| charset.contains(ch)))
+                .count() as u64;
+            if left < *length {
+                return 0.0;
+            }
+            (0..*length)
+                .map(|i| (left - i) as f64 / (size - i) as f64)
+                .product()
+        });
+
+        if accepted_share > 0.0 {
+            Some(all_bits + accepted_share.log2())
+        } else {
+            Some(0.0)
+        }
+    }
+
+    // Share of the strings with at least one character of every required charset,
+    // counted by the inclusion–exclusion principle from the shares of the strings
+    // without the characters of every subset of the required charsets.
+    fn accepted_share(required: &[&str], share: impl Fn(&[&str]) -> f64) -> f64 {
+        let mut accepted_share = 0.0;
+        for subset in 0..(1u32 << required.len()) {
+            let excluded: Vec<&str> = required
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| subset & (1 << i) != 0)
+                .map(|(_, charset)| *charset)
+                .collect();
+            if excluded.len().is_multiple_of(2) {
+                accepted_share += share(&excluded);
+            } else {
+                accepted_share -= share(&excluded);
+            }
+        }
+        accepted_share
+    }
+}
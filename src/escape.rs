@@ -0,0 +1,137 @@
+pub mod escape {
+    use std::fmt::Write;
+
+    /// Quote the password for a POSIX shell with single quotes.
+    ///
+    /// Nothing is expanded inside single quotes, the quote itself is written as `'\''`.
+    pub fn shell_single_quote(password: &str) -> String {
+        format!("'{}'", password.replace('\'', "'\\''"))
+    }
+
+    /// Render the password as a JSON string with the quotes.
+    ///
+    /// Line and paragraph separators are escaped too, so the result is also
+    /// a valid JavaScript string literal.
+    pub fn json_string(password: &str) -> String {
+        let mut result = String::from("\"");
+        for ch in password.chars() {
+            match ch {
+                '"' => result.push_str("\\\""),
+                '\\' => result.push_str("\\\\"),
+                '\n' => result.push_str("\\n"),
+                '\r' => result.push_str("\\r"),
+                '\t' => result.push_str("\\t"),
+                '\u{8}' => result.push_str("\\b"),
+                '\u{c}' => result.push_str("\\f"),
+                '\u{0}'..='\u{1f}' | '\u{2028}' | '\u{2029}' => {
+                    let _ = write!(result, "\\u{:04x}", ch as u32);
+                }
+                _ => result.push(ch),
+            }
+        }
+        result.push('"');
+        result
+    }
+
+    /// Percent-encode the password for a URL, like its userinfo or query part.
+    ///
+    /// Only the unreserved characters of RFC 3986 are kept,
+    /// other characters are encoded by the bytes of their UTF-8 form.
+    pub fn url_percent_encode(password: &str) -> String {
+        let mut result = String::new();
+        for byte in password.bytes() {
+            if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+                result.push(byte as char);
+            } else {
+                let _ = write!(result, "%{:02X}", byte);
+            }
+        }
+        result
+    }
+
+    /// Render the password as a double-quoted YAML scalar.
+    ///
+    /// Characters which are not printable in YAML are escaped.
+    pub fn yaml_scalar(password: &str) -> String {
+        let mut result = String::from("\"");
+        for ch in password.chars() {
+            match ch {
+                '"' => result.push_str("\\\""),
+                '\\' => result.push_str("\\\\"),
+                '\0' => result.push_str("\\0"),
+                '\t' => result.push_str("\\t"),
+                '\n' => result.push_str("\\n"),
+                '\r' => result.push_str("\\r"),
+                '\u{85}' => result.push_str("\\N"),
+                '\u{2028}' => result.push_str("\\L"),
+                '\u{2029}' => result.push_str("\\P"),
+                '\u{0}'..='\u{1f}' | '\u{7f}'..='\u{9f}' => {
+                    let _ = write!(result, "\\x{:02x}", ch as u32);
+                }
+                '\u{feff}' | '\u{fffe}' | '\u{ffff}' => {
+                    let _ = write!(result, "\\u{:04x}", ch as u32);
+                }
+                _ => result.push(ch),
+            }
+        }
+        result.push('"');
+        result
+    }
+
+    /// Render the password as an XML attribute value in double quotes.
+    ///
+    /// Tabs and line breaks are written as character references to survive
+    /// the attribute value normalization.
+    /// Returns `None` if the password contains characters not allowed in XML 1.0,
+    /// like control characters other than tabs and line breaks.
+    pub fn xml_attribute(password: &str) -> Option<String> {
+        let mut result = String::from("\"");
+        for ch in password.chars() {
+            match ch {
+                '&' => result.push_str("&amp;"),
+                '<' => result.push_str("&lt;"),
+                '>' => result.push_str("&gt;"),
+                '"' => result.push_str("&quot;"),
+                '\'' => result.push_str("&apos;"),
+                '\t' => result.push_str("&#9;"),
+                '\n' => result.push_str("&#10;"),
+                '\r' => result.push_str("&#13;"),
+                '\u{0}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}' => return None,
+                _ => result.push(ch),
+            }
+        }
+        result.push('"');
+        Some(result)
+    }
+
+    /// Render the password as a C string literal with the quotes.
+    ///
+    /// Non-ASCII characters are written as octal escapes of their UTF-8 bytes,
+    /// so the literal doesn't depend on the encoding of the source file.
+    pub fn c_string_literal(password: &str) -> String {
+        let mut result = String::from("\"");
+        for byte in password.bytes() {
+            match byte {
+                b'"' => result.push_str("\\\""),
+                b'\\' => result.push_str("\\\\"),
+                b'\n' => result.push_str("\\n"),
+                b'\r' => result.push_str("\\r"),
+                b'\t' => result.push_str("\\t"),
+                // Avoids trigraphs like `??/`
+                b'?' => result.push_str("\\?"),
+                // Octal escapes take at most 3 digits, unlike the hexadecimal ones
+                0x00..=0x1f | 0x7f..=0xff => {
+                    let _ = write!(result, "\\{:03o}", byte);
+                }
+                _ => result.push(byte as char),
+            }
+        }
+        result.push('"');
+        result
+    }
+
+    /// Render the password as a Rust string literal with the quotes.
+    pub fn rust_string_literal(password: &str) -> String {
+        format!("\"{}\"", password.escape_debug())
+    }
+}
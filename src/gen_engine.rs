@@ -1,8 +1,11 @@
 pub mod gen_engine {
     use crate::Passgen;
-    use rand::{Rng, SeedableRng};
+    use crate::token::token::CROCKFORD_ALPHABET;
+    use rand::{Rng, RngCore, SeedableRng};
     use rand_hc::Hc128Rng;
     use rand_isaac::Isaac64Rng;
+    use std::fmt;
+    use std::time::{SystemTime, UNIX_EPOCH};
 
     // Letters charset.
     pub const LETTERS_CHARSET: &str = "abcdefghijklmnopqrstuvwxyz";
@@ -21,14 +24,307 @@ pub mod gen_engine {
     // Set without ambiguous and inconvenient letters.
     pub const STRONG_USAB_LETTERS_CHARSET: &str = "ABCDEFGHJKMNPQRSTUVWXYZabcdefghjkmnpqrstuvwxyz";
 
+    // URL-safe alphabet of NanoID.
+    pub const NANOID_ALPHABET: &str =
+        "useandom-26T198340PX75pxJACKVERYMINDBUSHWOLF_GQZbfghjklqvwyzrict";
+    // Default size of NanoID.
+    pub const NANOID_SIZE: usize = 21;
+    // Default limit of the results generated while looking for a strong enough one.
+    pub const DEFAULT_MAX_ATTEMPTS: u32 = 1000;
+
+    /// Error of the password generation.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum GenerationError {
+        /// No charset is enabled, so there is nothing to generate from.
+        CleanRuleset,
+        /// No character is left for the position with the index of the result of the length,
+        /// for example the `safe_contexts` removed all symbols of the last position
+        /// of the *"Strong & usability"* rule.
+        EmptyPosition { index: u32, length: u32 },
+        /// The `group_separator` needs escaping in some of the `safe_contexts`.
+        UnsafeSeparator,
+        /// No result of the length satisfied the rules in the allowed number of attempts,
+        /// for example there are not enough characters for a result without repeated ones.
+        RulesNotSatisfied { length: u32, attempts: u32 },
+        /// No result of the ruleset and the length reached the minimal strength score
+        /// in the allowed number of attempts.
+        StrengthNotReached {
+            min_score: u8,
+            length: u32,
+            attempts: u32,
+        },
+        /// The entropy of the results can't be computed exactly under the rules,
+        /// like `enab_no_sequences`, so the length reaching the requested entropy is unknown.
+        EntropyUnknown,
+        /// No length of the results reaches the requested entropy,
+        /// for example there are not enough characters for a result without repeated ones.
+        EntropyNotReached,
+    }
+
+    impl fmt::Display for GenerationError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                GenerationError::CleanRuleset => write!(f, "no charset is enabled"),
+                GenerationError::EmptyPosition { index, length } => write!(
+                    f,
+                    "no character is left for position {index} of a result of length {length}"
+                ),
+                GenerationError::UnsafeSeparator => {
+                    write!(f, "the group separator is not safe in the target contexts")
+                }
+                GenerationError::RulesNotSatisfied { length, attempts } => write!(
+                    f,
+                    "no result of length {length} satisfied the rules in {attempts} attempts"
+                ),
+                GenerationError::StrengthNotReached {
+                    min_score,
+                    length,
+                    attempts,
+                } => write!(
+                    f,
+                    "no result of length {length} reached the strength score {min_score} in {attempts} attempts"
+                ),
+                GenerationError::EntropyUnknown => {
+                    write!(
+                        f,
+                        "the entropy of the results can't be computed under the rules"
+                    )
+                }
+                GenerationError::EntropyNotReached => {
+                    write!(f, "no length of the results reaches the requested entropy")
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for GenerationError {}
+
+    /// Positions of the result a [PositionConstraint] applies to.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Positions {
+        /// The first position.
+        First,
+        /// The last position.
+        Last,
+        /// Position by index from the start, 0 is the first one.
+        Index(u32),
+        /// Position by index from the end, 0 is the last one.
+        FromEnd(u32),
+        /// Positions from the `start` index up to but not including the `end` index.
+        Range { start: u32, end: u32 },
+        /// Every `step`-th position starting from the `start` index, like every 4th one.
+        Every { start: u32, step: u32 },
+        /// All positions.
+        All,
+    }
+
+    impl Positions {
+        /// Check if the position with the index belongs to the result of the length.
+        pub fn contains(&self, index: u32, length: u32) -> bool {
+            if index >= length {
+                return false;
+            }
+            match *self {
+                Positions::First => index == 0,
+                Positions::Last => index + 1 == length,
+                Positions::Index(i) => index == i,
+                Positions::FromEnd(i) => i < length && index == length - 1 - i,
+                Positions::Range { start, end } => start <= index && index < end,
+                Positions::Every { start, step } => {
+                    index >= start && (index - start).is_multiple_of(step.max(1))
+                }
+                Positions::All => true,
+            }
+        }
+    }
+
+    /// Rule for the characters of the positions of a [PositionConstraint].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum PositionRule {
+        /// Characters of the positions are drawn only from this charset.
+        Only(&'static str),
+        /// Characters of this charset are not used at the positions.
+        Deny(&'static str),
+    }
+
+    /// Constraint of the characters at some positions of the result,
+    /// like "must not start with a digit or `-`" or "must end with a letter".
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct PositionConstraint {
+        pub positions: Positions,
+        pub rule: PositionRule,
+    }
+
+    /// How the length of the result is drawn by `generate_range`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum LengthDistribution {
+        /// Every length of the range is equally likely.
+        Uniform,
+        /// Lengths are weighted by the number of possible results of that length,
+        /// so every possible result of the whole range is equally likely.
+        /// Falls back to `Uniform` if the entropy of some length can't be computed.
+        Keyspace,
+    }
+
+    /// Get a new instance of the crate's CSPRNG: Hc128Rng seeded by Isaac64Rng seeded by the OS.
+    pub(crate) fn new_rng() -> Hc128Rng {
+        let mut isaac_seeder = Isaac64Rng::from_os_rng();
+        Hc128Rng::from_rng(&mut isaac_seeder)
+    }
+
+    /// Source of the time of the time-ordered identifiers, can be replaced for testing.
+    pub trait Clock {
+        /// Milliseconds since the Unix epoch.
+        fn now_millis(&self) -> u64;
+    }
+
+    /// Clock of the system time.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct SystemClock;
+
+    impl Clock for SystemClock {
+        fn now_millis(&self) -> u64 {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or(0)
+        }
+    }
+
+    /// Generate random bytes by the same CSPRNG as the passwords,
+    /// for key material, salts or nonces.
+    pub fn generate_bytes(len: usize) -> Vec<u8> {
+        let mut bytes = vec![0u8; len];
+        fill_bytes(&mut bytes);
+        bytes
+    }
+
+    /// Fill the buffer with random bytes by the same CSPRNG as the passwords.
+    pub fn fill_bytes(dest: &mut [u8]) {
+        new_rng().fill_bytes(dest);
+    }
+
     impl Passgen {
-        pub(crate) fn generate_pass(&mut self, res_len: u32) -> String {
-            let mut isaac_seeder = Isaac64Rng::from_os_rng();
-            let mut rng = Hc128Rng::from_rng(&mut isaac_seeder);
+        /// Returns `None` if no character of some position can follow the previous ones.
+        pub(crate) fn generate_pass(&mut self, res_len: u32) -> Option<String> {
+            let mut rng = new_rng();
+            let mut result: Vec<char> = Vec::with_capacity(res_len as usize);
+
+            for pool in self.position_pools(res_len) {
+                let allowed: Vec<char> = pool
+                    .into_iter()
+                    .filter(|&ch| self.can_follow(&result, ch))
+                    .collect();
+                if allowed.is_empty() {
+                    return None;
+                }
+                result.push(allowed[rng.random_range(0..allowed.len())]);
+            }
+
+            Some(result.into_iter().collect())
+        }
+
+        /// Check if the character can follow the characters before it under the rules
+        /// `enab_unique_chars`, `max_consecutive_chars` and `enab_no_sequences`.
+        pub(crate) fn can_follow(&self, before: &[char], ch: char) -> bool {
+            if self.enab_unique_chars && before.contains(&ch) {
+                return false;
+            }
+
+            if self.max_consecutive_chars > 0 {
+                let same = before.iter().rev().take_while(|&&c| c == ch).count();
+                if same >= self.max_consecutive_chars as usize {
+                    return false;
+                }
+            }
+
+            // The same sequences as penalized by the strength scoring, like `abc` or `321`
+            if self.enab_no_sequences
+                && let [.., c1, c2] = before
+            {
+                let (c1, c2, c3) = (*c1 as u32, *c2 as u32, ch as u32);
+                if (c2 == c1 + 1 && c3 == c2 + 1) || (c2 + 1 == c1 && c3 + 1 == c2) {
+                    return false;
+                }
+            }
+
+            true
+        }
+
+        /// Check if every character of the password can follow the characters before it.
+        pub(crate) fn validate_char_rules(&self, pass: &str) -> bool {
+            let chars: Vec<char> = pass.chars().collect();
+            (0..chars.len()).all(|i| self.can_follow(&chars[..i], chars[i]))
+        }
+
+        /// Get the set of characters every position of the result is drawn from.
+        pub(crate) fn position_pools(&self, res_len: u32) -> Vec<Vec<char>> {
+            let base = self.base_pool();
+            (0..res_len)
+                .map(|index| self.position_pool(&base, index, res_len))
+                .collect()
+        }
+
+        /// Get the distinct sets of characters the positions of the result are drawn from
+        /// with the number of positions drawn from each of them.
+        ///
+        /// Positions are not enumerated one by one: only the constraints of `Every`
+        /// make the sets differ between the boundaries of the constraints and they repeat
+        /// with the period of their steps, so a single position of every class is enough.
+        pub(crate) fn grouped_position_pools(&self, res_len: u32) -> Vec<(Vec<char>, u64)> {
+            let len = res_len as u64;
+            let mut bounds: Vec<u64> = vec![0, len];
+            if self.enab_strong_usab {
+                bounds.extend([1, len.saturating_sub(1)]);
+            }
+            let mut period: u64 = 1;
+            for constraint in &self.position_constraints {
+                match constraint.positions {
+                    Positions::First => bounds.push(1),
+                    Positions::Last => bounds.push(len.saturating_sub(1)),
+                    Positions::Index(i) => bounds.extend([i as u64, i as u64 + 1]),
+                    Positions::FromEnd(i) => bounds.extend([
+                        len.saturating_sub(i as u64 + 1),
+                        len.saturating_sub(i as u64),
+                    ]),
+                    Positions::Range { start, end } => bounds.extend([start as u64, end as u64]),
+                    Positions::Every { start, step } => {
+                        bounds.push(start as u64);
+                        // Period longer than the result means every position is its own class
+                        let step = step.max(1) as u64;
+                        period = (period / gcd(period, step))
+                            .checked_mul(step)
+                            .map_or(len.max(1), |lcm| lcm.min(len.max(1)));
+                    }
+                    Positions::All => {}
+                }
+            }
+            bounds.retain(|&bound| bound <= len);
+            bounds.sort_unstable();
+            bounds.dedup();
+
+            let base = self.base_pool();
+            let mut groups: Vec<(Vec<char>, u64)> = Vec::new();
+            for segment in bounds.windows(2) {
+                let (from, to) = (segment[0], segment[1]);
+                for index in from..to.min(from + period) {
+                    let count = (to - index).div_ceil(period);
+                    let pool = self.position_pool(&base, index as u32, res_len);
+                    match groups.iter_mut().find(|(other, _)| *other == pool) {
+                        Some((_, total)) => *total += count,
+                        None => groups.push((pool, count)),
+                    }
+                }
+            }
+            groups
+        }
+
+        // Characters of the enabled charsets or the custom charset before the position rules.
+        fn base_pool(&self) -> Vec<char> {
             let mut pass_assembly: Vec<char> = Vec::new();
 
-            if self.custom_charset.len() != 0 {
-                let mut cc_vec: Vec<char> = self.custom_charset.chars().into_iter().collect();
+            if !self.custom_charset.is_empty() {
+                let mut cc_vec: Vec<char> = self.custom_charset.chars().collect();
                 pass_assembly.append(&mut cc_vec);
             } else if self.enab_strong_usab
                 || (!self.enab_letters
@@ -36,51 +332,177 @@ pub mod gen_engine {
                     && !self.enab_num
                     && !self.enab_spec_symbs)
             {
-                let mut suc_vec: Vec<char> = STRONG_USAB_CHARSET.chars().into_iter().collect();
-                    pass_assembly.append(&mut suc_vec);
+                let mut suc_vec: Vec<char> = STRONG_USAB_CHARSET.chars().collect();
+                pass_assembly.append(&mut suc_vec);
             } else {
                 if self.enab_letters {
-                    let mut el_vec: Vec<char> = LETTERS_CHARSET.chars().into_iter().collect();
+                    let mut el_vec: Vec<char> = LETTERS_CHARSET.chars().collect();
                     pass_assembly.append(&mut el_vec);
                 }
                 if self.enab_u_letters {
-                    let mut eul_vec: Vec<char> = U_LETTERS_CHARSET.chars().into_iter().collect();
+                    let mut eul_vec: Vec<char> = U_LETTERS_CHARSET.chars().collect();
                     pass_assembly.append(&mut eul_vec);
                 }
                 if self.enab_num {
-                    let mut en_vec: Vec<char> = NUM_CHARSET.chars().into_iter().collect();
+                    let mut en_vec: Vec<char> = NUM_CHARSET.chars().collect();
                     pass_assembly.append(&mut en_vec);
                 }
                 if self.enab_spec_symbs {
-                    let mut ess_vec: Vec<char> = SPEC_SYMB_CHARSET.chars().into_iter().collect();
+                    let mut ess_vec: Vec<char> = SPEC_SYMB_CHARSET.chars().collect();
                     pass_assembly.append(&mut ess_vec);
                 }
             }
+            pass_assembly
+        }
 
-            let mut pass_candidate_vec: Vec<char> = Vec::new();
+        // Characters the position with the index is drawn from.
+        fn position_pool(&self, base: &[char], index: u32, res_len: u32) -> Vec<char> {
+            let mut pool = base.to_vec();
 
-            if self.enab_strong_usab {
-                let letters_charset: Vec<char> =
-                    STRONG_USAB_LETTERS_CHARSET.chars().into_iter().collect();
-                let simp_symb_charset: Vec<char> = SIMP_SYMB_CHARSET.chars().into_iter().collect();
+            if self.enab_strong_usab && res_len >= 2 {
+                if index == 0 {
+                    // first pass symbol from all letters
+                    pool = STRONG_USAB_LETTERS_CHARSET.chars().collect();
+                } else if index == res_len - 1 {
+                    // last pass symbol from simple symbols
+                    pool = SIMP_SYMB_CHARSET.chars().collect();
+                }
+            }
 
-                // gen first pass symbol from all letters
-                pass_candidate_vec.push(letters_charset[rng.random_range(0..letters_charset.len())]);
+            // Constraints are applied in the order they were added,
+            // then the characters unsafe in the target contexts are removed
+            for constraint in &self.position_constraints {
+                if !constraint.positions.contains(index, res_len) {
+                    continue;
+                }
+                match constraint.rule {
+                    PositionRule::Only(charset) => {
+                        // Repeated characters would make some of them more likely
+                        pool.clear();
+                        for ch in charset.chars() {
+                            if !pool.contains(&ch) {
+                                pool.push(ch);
+                            }
+                        }
+                    }
+                    PositionRule::Deny(charset) => pool.retain(|&ch| !charset.contains(ch)),
+                }
+            }
+            pool.retain(|&ch| {
+                self.safe_contexts
+                    .iter()
+                    .all(|context| context.is_char_safe(ch))
+            });
+            pool
+        }
 
-                // gen main pass body
-                for _ in 0..(res_len - 2) {
-                    pass_candidate_vec.push(pass_assembly[rng.random_range(0..pass_assembly.len())]);
+        /// Check if every character of the password can be drawn at its position by `generate`:
+        /// the *"Strong & usability"* rule, the position constraints and the safe contexts.
+        /// Characters outside the enabled charsets are allowed, unlike the ones
+        /// outside a custom charset.
+        pub(crate) fn validate_position_pools(&self, pass: &str) -> bool {
+            let base = self.base_pool();
+            let length = pass.chars().count() as u32;
+            pass.chars().enumerate().all(|(index, ch)| {
+                let mut base = base.clone();
+                if self.custom_charset.is_empty() {
+                    base.push(ch);
                 }
+                self.position_pool(&base, index as u32, length)
+                    .contains(&ch)
+            })
+        }
 
-                // gen last pass symbol from simple symbols
-                pass_candidate_vec
-                    .push(simp_symb_charset[rng.random_range(0..simp_symb_charset.len())]);
-            } else {
-                pass_candidate_vec = (0..res_len)
-                    .map(|_| pass_assembly[rng.random_range(0..pass_assembly.len())])
-                    .collect();
+        /// Get the charsets a result must contain at least one character from.
+        /// Results of a custom charset are not checked.
+        pub(crate) fn required_charsets(&self) -> Vec<&'static str> {
+            let mut required = Vec::new();
+            if self.enab_letters || self.enab_strong_usab {
+                required.push(LETTERS_CHARSET);
             }
-            String::from_iter(pass_candidate_vec)
+            if self.enab_u_letters || self.enab_strong_usab {
+                required.push(U_LETTERS_CHARSET);
+            }
+            if self.enab_num || self.enab_strong_usab {
+                required.push(NUM_CHARSET);
+            }
+            if self.enab_spec_symbs || self.enab_strong_usab {
+                required.push(SPEC_SYMB_CHARSET);
+            }
+            required
+        }
+    }
+
+    impl Passgen {
+        /// Generate a random UUID of version 4, like `7f1c2a9e-3b4d-4c5e-9f60-718293a4b5c6`.
+        /// The result is automatically stored in the `password` field.
+        pub fn generate_uuid_v4(&mut self) -> String {
+            let mut value = new_rng().random::<u128>();
+            value = (value & !(0xF << 76)) | (0x4 << 76);
+            value = (value & !(0x3 << 62)) | (0x2 << 62);
+            self.password = format_uuid(value);
+            self.password.clone()
+        }
+
+        /// Generate a time-ordered UUID of version 7: 48 bits of the Unix time in milliseconds
+        /// from the clock followed by 74 random bits.
+        /// The result is automatically stored in the `password` field.
+        pub fn generate_uuid_v7(&mut self, clock: &dyn Clock) -> String {
+            let time = (clock.now_millis() & 0xFFFF_FFFF_FFFF) as u128;
+            let mut value = (time << 80) | (new_rng().random::<u128>() & ((1 << 80) - 1));
+            value = (value & !(0xF << 76)) | (0x7 << 76);
+            value = (value & !(0x3 << 62)) | (0x2 << 62);
+            self.password = format_uuid(value);
+            self.password.clone()
+        }
+
+        /// Generate a ULID: 48 bits of the Unix time in milliseconds from the clock
+        /// followed by 80 random bits, 26 characters of Crockford's Base32.
+        /// The result is automatically stored in the `password` field.
+        pub fn generate_ulid(&mut self, clock: &dyn Clock) -> String {
+            let time = (clock.now_millis() & 0xFFFF_FFFF_FFFF) as u128;
+            let value = (time << 80) | (new_rng().random::<u128>() & ((1 << 80) - 1));
+            let alphabet = CROCKFORD_ALPHABET.as_bytes();
+            // 26 characters of 5 bits hold 130 bits, the first one holds only 3
+            self.password = (0..26)
+                .map(|i| alphabet[((value >> (125 - 5 * i)) & 0x1F) as usize] as char)
+                .collect();
+            self.password.clone()
+        }
+
+        /// Generate a NanoID of the size from the alphabet, for example
+        /// of the [NANOID_ALPHABET] and the [NANOID_SIZE].
+        /// Every character is drawn uniformly from the alphabet.
+        /// The result is automatically stored in the `password` field.
+        pub fn generate_nanoid(&mut self, alphabet: &str, size: usize) -> String {
+            let alphabet: Vec<char> = alphabet.chars().collect();
+            let mut rng = new_rng();
+            self.password = if alphabet.is_empty() {
+                String::new()
+            } else {
+                (0..size)
+                    .map(|_| alphabet[rng.random_range(0..alphabet.len())])
+                    .collect()
+            };
+            self.password.clone()
         }
     }
+
+    // Greatest common divisor of the steps of the positions.
+    fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 { a } else { gcd(b, a % b) }
+    }
+
+    // Format the 128 bits as a UUID in lowercase hexadecimal.
+    fn format_uuid(value: u128) -> String {
+        let hex = format!("{:032x}", value);
+        format!(
+            "{}-{}-{}-{}-{}",
+            &hex[0..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..32]
+        )
+    }
 }
@@ -0,0 +1,52 @@
+pub mod grouping {
+    use crate::Passgen;
+
+    /// Split the text into groups of the size joined by the separator,
+    /// like `XXXX-XXXX-XXXX`. The last group can be shorter.
+    /// The text is returned as is if the size is 0.
+    pub fn format_groups(text: &str, group_size: u32, separator: &str) -> String {
+        if group_size == 0 {
+            return text.to_string();
+        }
+        let chars: Vec<char> = text.chars().collect();
+        chars
+            .chunks(group_size as usize)
+            .map(String::from_iter)
+            .collect::<Vec<String>>()
+            .join(separator)
+    }
+
+    /// Strip the separators of the text formatted by [format_groups] back out.
+    ///
+    /// Separators are expected only between the groups of the size, so characters
+    /// equal to the separator inside the groups are kept.
+    /// Returns `None` if the text is not formatted by the groups of the size.
+    pub fn parse_groups(text: &str, group_size: u32, separator: &str) -> Option<String> {
+        if group_size == 0 || separator.is_empty() {
+            return Some(text.to_string());
+        }
+        let mut result = String::new();
+        let mut rest = text;
+        loop {
+            let group: String = rest.chars().take(group_size as usize).collect();
+            rest = &rest[group.len()..];
+            result.push_str(&group);
+            if rest.is_empty() {
+                return Some(result);
+            }
+            rest = rest.strip_prefix(separator)?;
+            if rest.is_empty() {
+                return None;
+            }
+        }
+    }
+
+    impl Passgen {
+        /// Get the password without the separators of the groups, if it is grouped
+        /// by the `group_size` and the `group_separator`.
+        pub fn ungrouped_password(&self) -> String {
+            parse_groups(&self.password, self.group_size, self.group_separator)
+                .unwrap_or_else(|| self.password.clone())
+        }
+    }
+}
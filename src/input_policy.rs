@@ -0,0 +1,94 @@
+pub mod input_policy {
+    use unicode_general_category::{GeneralCategory, get_general_category};
+    use unicode_normalization::UnicodeNormalization;
+
+    /// Policy for the passwords entered by users.
+    ///
+    /// It is applied by `set_password` before validation and strength checking.
+    /// The default policy keeps the password as is and allows any characters.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct InputPolicy {
+        /// Apply Unicode NFKC normalization to the password.
+        /// Passwords typed on different clients in different normalization forms
+        /// become the same string.
+        pub nfkc: bool,
+
+        /// Allow whitespace characters, like a space.
+        pub allow_whitespace: bool,
+
+        /// Allow control characters (`Cc` category), like a tab or a line feed.
+        pub allow_control: bool,
+
+        /// Allow invisible format characters (`Cf` category), like a zero-width joiner.
+        pub allow_format: bool,
+
+        /// Allow code points which are not assigned in Unicode yet.
+        pub allow_unassigned: bool,
+
+        /// Allow private-use code points.
+        pub allow_private_use: bool,
+    }
+
+    impl Default for InputPolicy {
+        fn default() -> InputPolicy {
+            InputPolicy {
+                nfkc: false,
+                allow_whitespace: true,
+                allow_control: true,
+                allow_format: true,
+                allow_unassigned: true,
+                allow_private_use: true,
+            }
+        }
+    }
+
+    impl InputPolicy {
+        /// Policy recommended by NIST SP 800-63B.
+        ///
+        /// NFKC normalization is applied, spaces and emoji are allowed,
+        /// but control characters, unassigned and private-use code points are not.
+        pub fn nist() -> InputPolicy {
+            InputPolicy {
+                nfkc: true,
+                allow_whitespace: true,
+                allow_control: false,
+                allow_format: true,
+                allow_unassigned: false,
+                allow_private_use: false,
+            }
+        }
+
+        /// Normalize the password according to the policy.
+        pub fn normalize(&self, password: &str) -> String {
+            if self.nfkc {
+                password.nfkc().collect()
+            } else {
+                password.to_string()
+            }
+        }
+
+        /// Check if the character is allowed by the policy.
+        pub fn is_char_allowed(&self, ch: char) -> bool {
+            match get_general_category(ch) {
+                // Tabs and line breaks are control characters, not just whitespace
+                GeneralCategory::Control => self.allow_control,
+                _ if ch.is_whitespace() => self.allow_whitespace,
+                GeneralCategory::Format => self.allow_format,
+                GeneralCategory::Unassigned => self.allow_unassigned,
+                GeneralCategory::PrivateUse => self.allow_private_use,
+                _ => true,
+            }
+        }
+
+        /// Check if all characters of the password are allowed by the policy.
+        pub fn is_allowed(&self, password: &str) -> bool {
+            password.chars().all(|ch| self.is_char_allowed(ch))
+        }
+    }
+
+    /// Check if the character is invisible and doesn't add to the strength of a password,
+    /// like a zero-width joiner.
+    pub fn is_ignorable(ch: char) -> bool {
+        get_general_category(ch) == GeneralCategory::Format
+    }
+}
@@ -0,0 +1,170 @@
+pub mod keyboard {
+    use crate::lang::lang::Language;
+
+    /// Keyboard layouts checked for keyboard walks like `qwerty` or `1qaz2wsx`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum KeyboardLayout {
+        /// English QWERTY
+        Qwerty,
+        /// German QWERTZ
+        Qwertz,
+        /// French AZERTY
+        Azerty,
+        /// Russian ЙЦУКЕН
+        Jcuken,
+    }
+
+    /// All supported keyboard layouts.
+    pub const KEYBOARD_LAYOUTS: [KeyboardLayout; 4] = [
+        KeyboardLayout::Qwerty,
+        KeyboardLayout::Qwertz,
+        KeyboardLayout::Azerty,
+        KeyboardLayout::Jcuken,
+    ];
+
+    // Walks shorter than this are too common in random passwords.
+    pub const MIN_WALK_LENGTH: usize = 4;
+
+    // Horizontal offsets of the keyboard rows in quarters of a key width.
+    const ROW_OFFSETS: [i32; 4] = [0, 2, 3, 5];
+
+    impl KeyboardLayout {
+        /// Keys of the layout by rows from the number row to the bottom row.
+        pub fn rows(&self) -> [&'static str; 4] {
+            match self {
+                KeyboardLayout::Qwerty => {
+                    ["1234567890-=", "qwertyuiop[]", "asdfghjkl;'", "zxcvbnm,./"]
+                }
+                KeyboardLayout::Qwertz => {
+                    ["1234567890ß", "qwertzuiopü+", "asdfghjklöä#", "yxcvbnm,.-"]
+                }
+                KeyboardLayout::Azerty => [
+                    "&é\"'(-è_çà)=",
+                    "azertyuiop^$",
+                    "qsdfghjklmù*",
+                    "wxcvbn,;:!",
+                ],
+                KeyboardLayout::Jcuken => {
+                    ["1234567890-=", "йцукенгшщзхъ", "фывапролджэ", "ячсмитьбю."]
+                }
+            }
+        }
+
+        /// Keys of the layout with Shift pressed by rows from the number row to the bottom row.
+        pub fn shifted_rows(&self) -> [&'static str; 4] {
+            match self {
+                KeyboardLayout::Qwerty => {
+                    ["!@#$%^&*()_+", "QWERTYUIOP{}", "ASDFGHJKL:\"", "ZXCVBNM<>?"]
+                }
+                KeyboardLayout::Qwertz => {
+                    ["!\"§$%&/()=?", "QWERTZUIOPÜ*", "ASDFGHJKLÖÄ'", "YXCVBNM;:_"]
+                }
+                KeyboardLayout::Azerty => {
+                    ["1234567890°+", "AZERTYUIOP¨£", "QSDFGHJKLM%µ", "WXCVBN?./§"]
+                }
+                KeyboardLayout::Jcuken => {
+                    ["!\"№;%:?*()_+", "ЙЦУКЕНГШЩЗХЪ", "ФЫВАПРОЛДЖЭ", "ЯЧСМИТЬБЮ,"]
+                }
+            }
+        }
+
+        /// Language the layout is used for, words of its dictionary are checked
+        /// for being typed on the other layouts.
+        pub fn language(&self) -> Language {
+            match self {
+                KeyboardLayout::Qwerty => Language::English,
+                KeyboardLayout::Qwertz => Language::German,
+                KeyboardLayout::Azerty => Language::French,
+                KeyboardLayout::Jcuken => Language::Russian,
+            }
+        }
+
+        /// Get the row and the column of the key which types the character.
+        pub fn key_position(&self, ch: char) -> Option<(usize, usize)> {
+            self.key_lookup(ch).map(|(row, col, _)| (row, col))
+        }
+
+        /// Get the character typed by the same key on the other layout.
+        ///
+        /// For example `g` on QWERTY is `п` on ЙЦУКЕН.
+        pub fn convert_char(&self, ch: char, to: KeyboardLayout) -> Option<char> {
+            let (row, col, shifted) = self.key_lookup(ch)?;
+            to.key_char(row, col, shifted)
+        }
+
+        fn key_lookup(&self, ch: char) -> Option<(usize, usize, bool)> {
+            for (shifted, rows) in [(false, self.rows()), (true, self.shifted_rows())] {
+                for (row, keys) in rows.iter().enumerate() {
+                    if let Some(col) = keys.chars().position(|key| key == ch) {
+                        return Some((row, col, shifted));
+                    }
+                }
+            }
+            None
+        }
+
+        /// Get the character typed by the key at the row and the column.
+        pub fn key_char(&self, row: usize, col: usize, shifted: bool) -> Option<char> {
+            let rows = if shifted {
+                self.shifted_rows()
+            } else {
+                self.rows()
+            };
+            rows.get(row)?.chars().nth(col)
+        }
+
+        /// Check if the characters are typed by neighbouring keys.
+        pub fn are_adjacent(&self, a: char, b: char) -> bool {
+            match (self.key_position(a), self.key_position(b)) {
+                (Some((row_a, col_a)), Some((row_b, col_b))) => {
+                    let x_a = ROW_OFFSETS[row_a] + col_a as i32 * 4;
+                    let x_b = ROW_OFFSETS[row_b] + col_b as i32 * 4;
+                    match row_a.abs_diff(row_b) {
+                        0 => (x_a - x_b).abs() == 4,
+                        1 => (x_a - x_b).abs() <= 4,
+                        _ => false,
+                    }
+                }
+                _ => false,
+            }
+        }
+    }
+
+    /// Find keyboard walks: runs of at least [MIN_WALK_LENGTH] characters
+    /// typed by neighbouring keys on any of the supported layouts.
+    ///
+    /// Returns the layout and the walk in the order they appear in the password.
+    /// If walks found on several layouts overlap, the longest one is returned.
+    pub fn find_keyboard_walks(password: &str) -> Vec<(KeyboardLayout, String)> {
+        let chars: Vec<char> = password.chars().collect();
+        let mut candidates: Vec<(KeyboardLayout, usize, usize)> = Vec::new();
+
+        for layout in KEYBOARD_LAYOUTS {
+            let mut start = 0;
+            for end in 1..=chars.len() {
+                if end < chars.len() && layout.are_adjacent(chars[end - 1], chars[end]) {
+                    continue;
+                }
+                if end - start >= MIN_WALK_LENGTH {
+                    candidates.push((layout, start, end));
+                }
+                start = end;
+            }
+        }
+
+        // Longest walks first, the order of layouts is kept for the walks of the same length
+        candidates.sort_by_key(|&(_, start, end)| std::cmp::Reverse(end - start));
+        let mut walks: Vec<(KeyboardLayout, usize, usize)> = Vec::new();
+        for (layout, start, end) in candidates {
+            if !walks.iter().any(|&(_, s, e)| start < e && s < end) {
+                walks.push((layout, start, end));
+            }
+        }
+        walks.sort_by_key(|&(_, start, _)| start);
+
+        walks
+            .into_iter()
+            .map(|(layout, start, end)| (layout, String::from_iter(&chars[start..end])))
+            .collect()
+    }
+}
@@ -1,4 +1,6 @@
 pub mod lang {
+    use crate::strength::strength::Suggestion;
+
     /// Supported languages for password strength level description
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     pub enum Language {
@@ -34,6 +36,25 @@ pub mod lang {
         Italian,
     }
 
+    /// All supported languages.
+    pub const LANGUAGES: [Language; 15] = [
+        Language::English,
+        Language::Chinese,
+        Language::Spanish,
+        Language::Hindi,
+        Language::Arabic,
+        Language::Portuguese,
+        Language::Bengali,
+        Language::Russian,
+        Language::Japanese,
+        Language::Punjabi,
+        Language::German,
+        Language::Korean,
+        Language::French,
+        Language::Turkish,
+        Language::Italian,
+    ];
+
     /// Translations for password strength levels
     pub struct StrengthTranslations;
 
@@ -130,4 +151,193 @@ pub mod lang {
             }
         }
     }
+
+    /// Translations for password improvement suggestions
+    pub struct SuggestionTranslations;
+
+    impl SuggestionTranslations {
+        /// Get the suggestion text in the selected language
+        pub fn get_text(language: Language, suggestion: Suggestion) -> &'static str {
+            match suggestion {
+                Suggestion::UseLongerPassword => match language {
+                    Language::English => "Use at least 12 characters",
+                    Language::Chinese => "至少使用12个字符",
+                    Language::Spanish => "Usa al menos 12 caracteres",
+                    Language::Hindi => "कम से कम 12 अक्षरों का उपयोग करें",
+                    Language::Arabic => "استخدم 12 حرفًا على الأقل",
+                    Language::Portuguese => "Use pelo menos 12 caracteres",
+                    Language::Bengali => "কমপক্ষে 12টি অক্ষর ব্যবহার করুন",
+                    Language::Russian => "Используйте не менее 12 символов",
+                    Language::Japanese => "12文字以上を使用してください",
+                    Language::Punjabi => "ਘੱਟੋ-ਘੱਟ 12 ਅੱਖਰ ਵਰਤੋ",
+                    Language::German => "Verwenden Sie mindestens 12 Zeichen",
+                    Language::Korean => "최소 12자 이상 사용하세요",
+                    Language::French => "Utilisez au moins 12 caractères",
+                    Language::Turkish => "En az 12 karakter kullanın",
+                    Language::Italian => "Usa almeno 12 caratteri",
+                },
+                Suggestion::AddLowercase => match language {
+                    Language::English => "Add a lowercase letter",
+                    Language::Chinese => "添加小写字母",
+                    Language::Spanish => "Añade una letra minúscula",
+                    Language::Hindi => "एक छोटा अक्षर जोड़ें",
+                    Language::Arabic => "أضف حرفًا صغيرًا",
+                    Language::Portuguese => "Adicione uma letra minúscula",
+                    Language::Bengali => "একটি ছোট হাতের অক্ষর যোগ করুন",
+                    Language::Russian => "Добавьте строчную букву",
+                    Language::Japanese => "小文字を追加してください",
+                    Language::Punjabi => "ਇੱਕ ਛੋਟਾ ਅੱਖਰ ਜੋੜੋ",
+                    Language::German => "Fügen Sie einen Kleinbuchstaben hinzu",
+                    Language::Korean => "소문자를 추가하세요",
+                    Language::French => "Ajoutez une lettre minuscule",
+                    Language::Turkish => "Bir küçük harf ekleyin",
+                    Language::Italian => "Aggiungi una lettera minuscola",
+                },
+                Suggestion::AddUppercase => match language {
+                    Language::English => "Add a capital letter",
+                    Language::Chinese => "添加大写字母",
+                    Language::Spanish => "Añade una letra mayúscula",
+                    Language::Hindi => "एक बड़ा अक्षर जोड़ें",
+                    Language::Arabic => "أضف حرفًا كبيرًا",
+                    Language::Portuguese => "Adicione uma letra maiúscula",
+                    Language::Bengali => "একটি বড় হাতের অক্ষর যোগ করুন",
+                    Language::Russian => "Добавьте заглавную букву",
+                    Language::Japanese => "大文字を追加してください",
+                    Language::Punjabi => "ਇੱਕ ਵੱਡਾ ਅੱਖਰ ਜੋੜੋ",
+                    Language::German => "Fügen Sie einen Großbuchstaben hinzu",
+                    Language::Korean => "대문자를 추가하세요",
+                    Language::French => "Ajoutez une lettre majuscule",
+                    Language::Turkish => "Bir büyük harf ekleyin",
+                    Language::Italian => "Aggiungi una lettera maiuscola",
+                },
+                Suggestion::AddDigit => match language {
+                    Language::English => "Add a digit",
+                    Language::Chinese => "添加数字",
+                    Language::Spanish => "Añade un número",
+                    Language::Hindi => "एक अंक जोड़ें",
+                    Language::Arabic => "أضف رقمًا",
+                    Language::Portuguese => "Adicione um número",
+                    Language::Bengali => "একটি সংখ্যা যোগ করুন",
+                    Language::Russian => "Добавьте цифру",
+                    Language::Japanese => "数字を追加してください",
+                    Language::Punjabi => "ਇੱਕ ਅੰਕ ਜੋੜੋ",
+                    Language::German => "Fügen Sie eine Ziffer hinzu",
+                    Language::Korean => "숫자를 추가하세요",
+                    Language::French => "Ajoutez un chiffre",
+                    Language::Turkish => "Bir rakam ekleyin",
+                    Language::Italian => "Aggiungi un numero",
+                },
+                Suggestion::AddSymbol => match language {
+                    Language::English => "Add a symbol",
+                    Language::Chinese => "添加符号",
+                    Language::Spanish => "Añade un símbolo",
+                    Language::Hindi => "एक चिह्न जोड़ें",
+                    Language::Arabic => "أضف رمزًا",
+                    Language::Portuguese => "Adicione um símbolo",
+                    Language::Bengali => "একটি চিহ্ন যোগ করুন",
+                    Language::Russian => "Добавьте символ",
+                    Language::Japanese => "記号を追加してください",
+                    Language::Punjabi => "ਇੱਕ ਚਿੰਨ੍ਹ ਜੋੜੋ",
+                    Language::German => "Fügen Sie ein Sonderzeichen hinzu",
+                    Language::Korean => "기호를 추가하세요",
+                    Language::French => "Ajoutez un symbole",
+                    Language::Turkish => "Bir sembol ekleyin",
+                    Language::Italian => "Aggiungi un simbolo",
+                },
+                Suggestion::AvoidSequences => match language {
+                    Language::English => "Avoid sequences like 'abc'",
+                    Language::Chinese => "避免使用类似 'abc' 的连续字符",
+                    Language::Spanish => "Evita secuencias como 'abc'",
+                    Language::Hindi => "'abc' जैसे क्रमों से बचें",
+                    Language::Arabic => "تجنب التسلسلات مثل 'abc'",
+                    Language::Portuguese => "Evite sequências como 'abc'",
+                    Language::Bengali => "'abc' এর মতো ক্রম এড়িয়ে চলুন",
+                    Language::Russian => "Избегайте последовательностей вроде 'abc'",
+                    Language::Japanese => "'abc' のような連続した文字は避けてください",
+                    Language::Punjabi => "'abc' ਵਰਗੇ ਕ੍ਰਮਾਂ ਤੋਂ ਬਚੋ",
+                    Language::German => "Vermeiden Sie Folgen wie 'abc'",
+                    Language::Korean => "'abc'와 같은 연속된 문자는 피하세요",
+                    Language::French => "Évitez les suites comme 'abc'",
+                    Language::Turkish => "'abc' gibi dizilerden kaçının",
+                    Language::Italian => "Evita sequenze come 'abc'",
+                },
+                Suggestion::AvoidRepeats => match language {
+                    Language::English => "Avoid repeated characters like 'aaa'",
+                    Language::Chinese => "避免使用类似 'aaa' 的重复字符",
+                    Language::Spanish => "Evita caracteres repetidos como 'aaa'",
+                    Language::Hindi => "'aaa' जैसे दोहराए गए अक्षरों से बचें",
+                    Language::Arabic => "تجنب الأحرف المكررة مثل 'aaa'",
+                    Language::Portuguese => "Evite caracteres repetidos como 'aaa'",
+                    Language::Bengali => "'aaa' এর মতো পুনরাবৃত্ত অক্ষর এড়িয়ে চলুন",
+                    Language::Russian => "Избегайте повторяющихся символов вроде 'aaa'",
+                    Language::Japanese => "'aaa' のような繰り返し文字は避けてください",
+                    Language::Punjabi => "'aaa' ਵਰਗੇ ਦੁਹਰਾਏ ਅੱਖਰਾਂ ਤੋਂ ਬਚੋ",
+                    Language::German => "Vermeiden Sie wiederholte Zeichen wie 'aaa'",
+                    Language::Korean => "'aaa'와 같은 반복 문자는 피하세요",
+                    Language::French => "Évitez les caractères répétés comme 'aaa'",
+                    Language::Turkish => "'aaa' gibi tekrarlanan karakterlerden kaçının",
+                    Language::Italian => "Evita caratteri ripetuti come 'aaa'",
+                },
+                Suggestion::AvoidCommonWords => match language {
+                    Language::English => "Avoid common passwords and words like 'password'",
+                    Language::Chinese => "避免使用类似 'password' 的常见密码和单词",
+                    Language::Spanish => "Evita contraseñas y palabras comunes como 'password'",
+                    Language::Hindi => "'password' जैसे सामान्य पासवर्ड और शब्दों से बचें",
+                    Language::Arabic => "تجنب كلمات المرور والكلمات الشائعة مثل 'password'",
+                    Language::Portuguese => "Evite senhas e palavras comuns como 'password'",
+                    Language::Bengali => "'password' এর মতো সাধারণ পাসওয়ার্ড ও শব্দ এড়িয়ে চলুন",
+                    Language::Russian => {
+                        "Избегайте распространённых паролей и слов вроде 'password'"
+                    }
+                    Language::Japanese => {
+                        "'password' のような一般的なパスワードや単語は避けてください"
+                    }
+                    Language::Punjabi => "'password' ਵਰਗੇ ਆਮ ਪਾਸਵਰਡਾਂ ਅਤੇ ਸ਼ਬਦਾਂ ਤੋਂ ਬਚੋ",
+                    Language::German => {
+                        "Vermeiden Sie gängige Passwörter und Wörter wie 'password'"
+                    }
+                    Language::Korean => "'password'와 같은 흔한 비밀번호와 단어는 피하세요",
+                    Language::French => {
+                        "Évitez les mots de passe et mots courants comme 'password'"
+                    }
+                    Language::Turkish => "'password' gibi yaygın şifre ve kelimelerden kaçının",
+                    Language::Italian => "Evita password e parole comuni come 'password'",
+                },
+                Suggestion::AvoidKeyboardPatterns => match language {
+                    Language::English => "Avoid keyboard patterns like 'qwerty'",
+                    Language::Chinese => "避免使用类似 'qwerty' 的键盘排列",
+                    Language::Spanish => "Evita patrones de teclado como 'qwerty'",
+                    Language::Hindi => "'qwerty' जैसे कीबोर्ड पैटर्न से बचें",
+                    Language::Arabic => "تجنب أنماط لوحة المفاتيح مثل 'qwerty'",
+                    Language::Portuguese => "Evite padrões de teclado como 'qwerty'",
+                    Language::Bengali => "'qwerty' এর মতো কীবোর্ড প্যাটার্ন এড়িয়ে চলুন",
+                    Language::Russian => "Избегайте клавиатурных комбинаций вроде 'йцукен'",
+                    Language::Japanese => "'qwerty' のようなキーボードの並びは避けてください",
+                    Language::Punjabi => "'qwerty' ਵਰਗੇ ਕੀਬੋਰਡ ਪੈਟਰਨਾਂ ਤੋਂ ਬਚੋ",
+                    Language::German => "Vermeiden Sie Tastaturmuster wie 'qwertz'",
+                    Language::Korean => "'qwerty'와 같은 키보드 패턴은 피하세요",
+                    Language::French => "Évitez les motifs de clavier comme 'azerty'",
+                    Language::Turkish => "'qwerty' gibi klavye desenlerinden kaçının",
+                    Language::Italian => "Evita sequenze di tasti come 'qwerty'",
+                },
+                Suggestion::AvoidDates => match language {
+                    Language::English => "Avoid dates and years like '1990'",
+                    Language::Chinese => "避免使用类似 '1990' 的日期和年份",
+                    Language::Spanish => "Evita fechas y años como '1990'",
+                    Language::Hindi => "'1990' जैसी तारीखों और वर्षों से बचें",
+                    Language::Arabic => "تجنب التواريخ والسنوات مثل '1990'",
+                    Language::Portuguese => "Evite datas e anos como '1990'",
+                    Language::Bengali => "'1990' এর মতো তারিখ ও সাল এড়িয়ে চলুন",
+                    Language::Russian => "Избегайте дат и годов вроде '1990'",
+                    Language::Japanese => "'1990' のような日付や年は避けてください",
+                    Language::Punjabi => "'1990' ਵਰਗੀਆਂ ਤਾਰੀਖਾਂ ਅਤੇ ਸਾਲਾਂ ਤੋਂ ਬਚੋ",
+                    Language::German => "Vermeiden Sie Daten und Jahreszahlen wie '1990'",
+                    Language::Korean => "'1990'과 같은 날짜와 연도는 피하세요",
+                    Language::French => "Évitez les dates et années comme '1990'",
+                    Language::Turkish => "'1990' gibi tarih ve yıllardan kaçının",
+                    Language::Italian => "Evita date e anni come '1990'",
+                },
+            }
+        }
+    }
 }
@@ -1,8 +1,38 @@
+#![allow(
+    clippy::module_inception,
+    clippy::new_without_default,
+    clippy::should_implement_trait
+)]
+
+pub mod api_key;
+pub mod check_digit;
+pub mod context;
+pub mod dictionary;
+pub mod entropy;
+pub mod escape;
 pub mod gen_engine;
+pub mod grouping;
+pub mod input_policy;
+pub mod keyboard;
 pub mod lang;
-
-use crate::gen_engine::gen_engine::{LETTERS_CHARSET, NUM_CHARSET, SPEC_SYMB_CHARSET, U_LETTERS_CHARSET};
+pub mod recovery;
+pub mod strength;
+pub mod token;
+
+use crate::context::context::SafeContext;
+use crate::dictionary::dictionary::DictionaryScope;
+use crate::gen_engine::gen_engine::{
+    DEFAULT_MAX_ATTEMPTS, GenerationError, LengthDistribution, PositionConstraint, PositionRule,
+    Positions, new_rng,
+};
+use crate::grouping::grouping::format_groups;
+use crate::input_policy::input_policy::InputPolicy;
 use crate::lang::lang::{Language, StrengthTranslations};
+use crate::strength::strength::fold_case;
+use rand::Rng;
+use std::fs;
+use std::io;
+use std::path::Path;
 
 /// Main [Passgen] structure.
 ///
@@ -39,6 +69,171 @@ use crate::lang::lang::{Language, StrengthTranslations};
 /// let result = Passgen::new().set_custom_charset("bla@.321").generate(8);
 /// ```
 ///
+/// You can get the exact entropy of the generated passwords:
+///
+/// ```
+/// use passgenlib::Passgen;
+/// let mut generator = Passgen::new();
+/// generator.set_enabled_letters(true);
+/// assert_eq!(generator.entropy_bits(10).unwrap().round(), 47.0);
+///
+/// let result = generator.generate_with_metadata(10);
+/// assert_eq!(result.password, generator.get_password());
+/// assert_eq!(result.entropy_bits, generator.entropy_bits(10));
+/// ```
+///
+/// You can generate a password of the smallest length reaching the requested entropy:
+///
+/// ```
+/// use passgenlib::Passgen;
+/// let mut generator = Passgen::new();
+/// generator.set_enabled_numbers(true);
+/// assert_eq!(generator.generate_with_entropy(128.0).unwrap().len(), 39);
+/// ```
+///
+/// You can generate a password of a random length within a range:
+///
+/// ```
+/// use passgenlib::Passgen;
+/// use passgenlib::gen_engine::gen_engine::LengthDistribution;
+/// let mut generator = Passgen::default();
+/// generator.set_length_distribution(LengthDistribution::Keyspace);
+/// let result = generator.generate_range(12, 16);
+/// assert!((12..=16).contains(&result.chars().count()));
+/// ```
+///
+/// You can generate only results which are not weak by the strength score:
+///
+/// ```
+/// use passgenlib::Passgen;
+/// use passgenlib::strength::strength::NOT_WEAK_MIN_SCORE;
+/// let mut generator = Passgen::default();
+/// generator.set_min_strength_score(NOT_WEAK_MIN_SCORE);
+/// let result = generator.try_generate(8).unwrap();
+/// assert!(generator.password_strength_score() >= NOT_WEAK_MIN_SCORE);
+/// ```
+///
+/// You can generate results without repeated or sequential characters:
+///
+/// ```
+/// use passgenlib::Passgen;
+/// let mut generator = Passgen::default();
+/// generator.set_enabled_unique_chars(true).set_enabled_no_sequences(true);
+/// let result = generator.generate(20);
+/// assert!(generator.validate_password());
+/// ```
+///
+/// You can restrict the characters at some positions of the result:
+///
+/// ```
+/// use passgenlib::Passgen;
+/// use passgenlib::gen_engine::gen_engine::{PositionRule, Positions};
+/// let mut generator = Passgen::default();
+/// generator.add_position_constraint(Positions::First, PositionRule::Deny("0123456789-"));
+/// let result = generator.generate(12);
+/// assert!(!result.starts_with(|ch: char| ch.is_ascii_digit() || ch == '-'));
+/// ```
+///
+/// You can generate results which are pasted into a shell or YAML without escaping:
+///
+/// ```
+/// use passgenlib::Passgen;
+/// use passgenlib::context::context::SafeContext;
+/// let mut generator = Passgen::default();
+/// generator.set_safe_contexts(&[SafeContext::Shell, SafeContext::Yaml]);
+/// let result = generator.generate(16);
+/// assert!(SafeContext::Shell.is_safe(&result) && SafeContext::Yaml.is_safe(&result));
+/// ```
+///
+/// You can render the result for pasting into a config:
+///
+/// ```
+/// use passgenlib::escape::escape::{json_string, shell_single_quote, url_percent_encode};
+/// assert_eq!(shell_single_quote("it's$"), "'it'\\''s$'");
+/// assert_eq!(json_string("a\"b\\"), "\"a\\\"b\\\\\"");
+/// assert_eq!(url_percent_encode("p@ss:w/rd"), "p%40ss%3Aw%2Frd");
+/// ```
+///
+/// You can generate a token of random bytes encoded by a binary-to-text encoding:
+///
+/// ```
+/// use passgenlib::Passgen;
+/// use passgenlib::token::token::TokenEncoding;
+/// let mut generator = Passgen::new();
+/// let result = generator.generate_token(32, TokenEncoding::Base64Url);
+/// assert_eq!(result.description(), "256-bit token, base64url, no padding");
+/// assert_eq!(TokenEncoding::Base64Url.decode(&result.token).unwrap().len(), 32);
+/// ```
+///
+/// You can get raw random bytes from the same CSPRNG, for example for a salt:
+///
+/// ```
+/// use passgenlib::gen_engine::gen_engine::{fill_bytes, generate_bytes};
+/// let salt = generate_bytes(16);
+/// let mut nonce = [0u8; 12];
+/// fill_bytes(&mut nonce);
+/// assert_eq!(salt.len(), 16);
+/// ```
+///
+/// You can generate API keys with a prefix and a checksum and verify them offline:
+///
+/// ```
+/// use passgenlib::Passgen;
+/// use passgenlib::api_key::api_key::{ApiKeyFormat, verify_api_key};
+/// let format = ApiKeyFormat::new("acme_live_");
+/// let key = Passgen::new().generate_api_key(&format);
+/// assert!(key.starts_with("acme_live_"));
+/// assert!(verify_api_key(&key, &format));
+/// ```
+///
+/// You can generate identifiers: UUIDv4, UUIDv7, ULID and NanoID:
+///
+/// ```
+/// use passgenlib::Passgen;
+/// use passgenlib::gen_engine::gen_engine::{NANOID_ALPHABET, NANOID_SIZE, SystemClock};
+/// let mut generator = Passgen::new();
+/// assert_eq!(generator.generate_uuid_v4().len(), 36);
+/// assert_eq!(generator.generate_uuid_v7(&SystemClock).len(), 36);
+/// assert_eq!(generator.generate_ulid(&SystemClock).len(), 26);
+/// assert_eq!(generator.generate_nanoid(NANOID_ALPHABET, NANOID_SIZE).len(), 21);
+/// ```
+///
+/// You can generate a set of unique recovery codes and verify the entered ones:
+///
+/// ```
+/// use passgenlib::Passgen;
+/// use passgenlib::recovery::recovery::{RecoveryCodeFormat, find_recovery_code};
+/// let format = RecoveryCodeFormat::default();
+/// let codes = Passgen::new().generate_recovery_codes(10, &format);
+/// assert_eq!(codes[0].len(), 9);
+/// let entered = codes[3].to_uppercase().replace('-', " ");
+/// assert_eq!(find_recovery_code(&entered, &codes, &format), Some(3));
+/// ```
+///
+/// You can split the result into groups for readability:
+///
+/// ```
+/// use passgenlib::Passgen;
+/// use passgenlib::grouping::grouping::parse_groups;
+/// let mut generator = Passgen::new();
+/// generator.set_enabled_uppercase_letters(true).set_grouping(4, "-");
+/// let result = generator.generate(12);
+/// assert_eq!(result.len(), 14);
+/// assert!(generator.validate_password());
+/// assert_eq!(parse_groups(&result, 4, "-").unwrap().len(), 12);
+/// ```
+///
+/// You can generate codes with a trailing check character which catches typos:
+///
+/// ```
+/// use passgenlib::Passgen;
+/// use passgenlib::check_digit::check_digit::CheckAlgorithm;
+/// let code = Passgen::new().generate_check_code(10, CheckAlgorithm::Damm);
+/// assert_eq!(code.len(), 10);
+/// assert!(CheckAlgorithm::Damm.validate(&code));
+/// assert_eq!(CheckAlgorithm::Luhn.check_char("7992739871"), Some('3'));
+/// ```
+///
 /// You can validate the existing password against the added rules:
 ///
 /// ```
@@ -49,6 +244,30 @@ use crate::lang::lang::{Language, StrengthTranslations};
 /// assert!(generator.validate_password());
 /// ```
 ///
+/// You can normalize entered passwords and restrict allowed characters:
+///
+/// ```
+/// use passgenlib::Passgen;
+/// use passgenlib::input_policy::input_policy::InputPolicy;
+/// let mut generator = Passgen::default();
+/// generator.set_input_policy(InputPolicy::nist());
+/// generator.set_password("Ｐａｓｓ１！");
+/// assert_eq!(generator.get_password(), "Pass1!");
+///
+/// generator.set_password("Pass\t1!");
+/// assert!(!generator.validate_password());
+/// ```
+///
+/// You can penalize organization specific words and optionally fail the validation:
+///
+/// ```
+/// use passgenlib::Passgen;
+/// let mut generator = Passgen::default();
+/// generator.add_blocklist_words(&["acme", "springfield"]).set_blocklist_validation(true);
+/// generator.set_password("@cme#Tulip9!");
+/// assert!(!generator.validate_password());
+/// ```
+///
 /// You can get password strength score:
 ///
 /// ```
@@ -59,13 +278,39 @@ use crate::lang::lang::{Language, StrengthTranslations};
 /// assert!(score >= 0 && score <= 100);
 /// ```
 ///
+/// You can get the breakdown of the password strength score:
+///
+/// ```
+/// use passgenlib::Passgen;
+/// let mut generator = Passgen::default();
+/// generator.set_password("Password123");
+/// let report = generator.password_strength_report();
+/// assert_eq!(report.score, generator.password_strength_score());
+/// for pattern in &report.patterns {
+///     println!("{:?} '{}': -{}", pattern.kind, pattern.matched, pattern.penalty);
+/// }
+/// ```
+///
+/// You can get suggestions for improving a weak password in the selected language:
+///
+/// ```
+/// use passgenlib::Passgen;
+/// use passgenlib::lang::lang::Language;
+/// let mut generator = Passgen::default();
+/// generator.set_password("abcdefg");
+/// assert_eq!(generator.password_suggestions()[0], "Use at least 12 characters");
+///
+/// generator.set_language(Language::German);
+/// assert_eq!(generator.password_suggestions()[0], "Verwenden Sie mindestens 12 Zeichen");
+/// ```
+///
 /// You can get password strength level in multiple languages:
 ///
 /// ```
 /// use passgenlib::Passgen;
 /// use passgenlib::lang::lang::{Language, StrengthTranslations};
 /// let mut generator = Passgen::default();
-/// generator.set_password("MyP@ssw0rd");
+/// generator.set_password("Tulip#Zebra9!");
 ///
 /// // English (default)
 /// assert_eq!(generator.password_strength_level(), "Strong");
@@ -121,6 +366,57 @@ pub struct Passgen {
     /// ⚙️If `"enab_strong_usab"` on too then you can generate combined strong and usability result with custom charset.
     pub custom_charset: &'static str,
 
+    /// 🔒Every character of the result is used only once.
+    ///
+    /// ⚠️`entropy_bits()` is unknown if the positions are drawn from different characters,
+    /// like under the *"Strong & usability"* rule.
+    pub enab_unique_chars: bool,
+
+    /// 🔒Maximum number of consecutive identical characters in the result, 0 means no limit.
+    ///
+    /// ⚠️`entropy_bits()` is unknown under this rule, unless `enab_unique_chars` is on.
+    pub max_consecutive_chars: u32,
+
+    /// 🔒No ascending or descending runs of 3 characters in the result, like `abc` or `321`.
+    ///
+    /// ⚠️`entropy_bits()` is unknown under this rule.
+    pub enab_no_sequences: bool,
+
+    /// 🔒Constraints of the characters at some positions of the result,
+    /// applied after the *"Strong & usability"* rule in the order they were added.
+    /// The validation checks them too, so the positions of several `Only` constraints
+    /// must be drawn from the charsets which agree with each other.
+    pub position_constraints: Vec<PositionConstraint>,
+
+    /// 🔒Target contexts the result is pasted into without escaping, like a shell or YAML.
+    /// Characters which need escaping in any of them are not used and fail the validation.
+    pub safe_contexts: Vec<SafeContext>,
+
+    /// Split the result into groups of this size, like `XXXX-XXXX-XXXX`, 0 means no groups.
+    /// The length, the entropy and the strength score of the result don't count
+    /// the separators and the validation strips them out.
+    pub group_size: u32,
+
+    /// Separator between the groups of the result. Default is `-`.
+    ///
+    /// ⚠️Nothing is generated if it needs escaping in the `safe_contexts`,
+    /// like `-` in YAML.
+    pub group_separator: &'static str,
+
+    /// How the length of the result is drawn by `generate_range()`.
+    /// Default is uniform.
+    pub length_distribution: LengthDistribution,
+
+    /// Minimal `password_strength_score()` of the generated results, 0 turns the check off.
+    /// Use `NOT_WEAK_MIN_SCORE` to never get results described as "Weak" or "Very Weak".
+    ///
+    /// ⚠️The rejected results are not taken into account by `entropy_bits()`.
+    pub min_strength_score: u8,
+
+    /// How many results are generated at most while looking for one
+    /// which satisfies the rules and has the `min_strength_score`. Default is 1000.
+    pub max_attempts: u32,
+
     /// Current password stored for validation and strength checking.
     /// This field is automatically populated when using the `generate()` method
     /// or manually set using the `set_password()` method.
@@ -129,6 +425,24 @@ pub struct Passgen {
     /// Language for password strength level descriptions.
     /// Default is English.
     pub language: Language,
+
+    /// Normalization and allowed characters for the passwords set with `set_password()`.
+    /// Default policy keeps the password as is and allows any characters.
+    pub input_policy: InputPolicy,
+
+    /// Organization specific words (company name, product names, city...)
+    /// which are penalized in the strength scoring, including case and leetspeak variants.
+    /// Words are stored case folded.
+    pub blocklist: Vec<String>,
+
+    /// Fail the password validation if the password contains a word from the `blocklist`.
+    pub enab_blocklist_validation: bool,
+
+    /// Which per-language dictionaries of common passwords are consulted by the strength scoring.
+    /// Default is the dictionary of the configured `language`.
+    ///
+    /// ⚠️ Dictionaries are enabled by the crate features `dict-<language>` or `dict-all`.
+    pub dictionary_scope: DictionaryScope,
 }
 
 impl Passgen {
@@ -141,8 +455,22 @@ impl Passgen {
             enab_spec_symbs: false,
             enab_strong_usab: false,
             custom_charset: "",
+            enab_unique_chars: false,
+            max_consecutive_chars: 0,
+            enab_no_sequences: false,
+            position_constraints: Vec::new(),
+            safe_contexts: Vec::new(),
+            group_size: 0,
+            group_separator: "-",
+            length_distribution: LengthDistribution::Uniform,
+            min_strength_score: 0,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
             password: String::new(),
             language: Language::English,
+            input_policy: InputPolicy::default(),
+            blocklist: Vec::new(),
+            enab_blocklist_validation: false,
+            dictionary_scope: DictionaryScope::Language,
         }
     }
 
@@ -155,8 +483,22 @@ impl Passgen {
             enab_spec_symbs: true,
             enab_strong_usab: false,
             custom_charset: "",
+            enab_unique_chars: false,
+            max_consecutive_chars: 0,
+            enab_no_sequences: false,
+            position_constraints: Vec::new(),
+            safe_contexts: Vec::new(),
+            group_size: 0,
+            group_separator: "-",
+            length_distribution: LengthDistribution::Uniform,
+            min_strength_score: 0,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
             password: String::new(),
             language: Language::English,
+            input_policy: InputPolicy::default(),
+            blocklist: Vec::new(),
+            enab_blocklist_validation: false,
+            dictionary_scope: DictionaryScope::Language,
         }
     }
 
@@ -175,9 +517,23 @@ impl Passgen {
             enab_num: false,
             enab_spec_symbs: false,
             custom_charset: "",
+            enab_unique_chars: false,
+            max_consecutive_chars: 0,
+            enab_no_sequences: false,
+            position_constraints: Vec::new(),
+            safe_contexts: Vec::new(),
+            group_size: 0,
+            group_separator: "-",
+            length_distribution: LengthDistribution::Uniform,
+            min_strength_score: 0,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
             enab_strong_usab: true,
             password: String::new(),
             language: Language::English,
+            input_policy: InputPolicy::default(),
+            blocklist: Vec::new(),
+            enab_blocklist_validation: false,
+            dictionary_scope: DictionaryScope::Language,
         }
     }
 
@@ -229,11 +585,79 @@ impl Passgen {
         self
     }
 
+    /// Set value of the field `enab_unique_chars` for `Passgen`.
+    pub fn set_enabled_unique_chars(&mut self, value: bool) -> &mut Passgen {
+        self.enab_unique_chars = value;
+        self
+    }
+
+    /// Set value of the field `max_consecutive_chars` for `Passgen`.
+    pub fn set_max_consecutive_chars(&mut self, value: u32) -> &mut Passgen {
+        self.max_consecutive_chars = value;
+        self
+    }
+
+    /// Set value of the field `enab_no_sequences` for `Passgen`.
+    pub fn set_enabled_no_sequences(&mut self, value: bool) -> &mut Passgen {
+        self.enab_no_sequences = value;
+        self
+    }
+
+    /// Add a constraint of the characters at the positions of the result.
+    pub fn add_position_constraint(
+        &mut self,
+        positions: Positions,
+        rule: PositionRule,
+    ) -> &mut Passgen {
+        self.position_constraints
+            .push(PositionConstraint { positions, rule });
+        self
+    }
+
+    /// Remove all constraints of the characters at the positions of the result.
+    pub fn clear_position_constraints(&mut self) -> &mut Passgen {
+        self.position_constraints.clear();
+        self
+    }
+
+    /// Set target contexts the result is pasted into without escaping.
+    pub fn set_safe_contexts(&mut self, contexts: &[SafeContext]) -> &mut Passgen {
+        self.safe_contexts = contexts.to_vec();
+        self
+    }
+
+    /// Set the size of the groups of the result and the separator between them.
+    pub fn set_grouping(&mut self, group_size: u32, separator: &'static str) -> &mut Passgen {
+        self.group_size = group_size;
+        self.group_separator = separator;
+        self
+    }
+
+    /// Set value of the field `length_distribution` for `Passgen`.
+    pub fn set_length_distribution(&mut self, value: LengthDistribution) -> &mut Passgen {
+        self.length_distribution = value;
+        self
+    }
+
+    /// Set value of the field `min_strength_score` for `Passgen`.
+    pub fn set_min_strength_score(&mut self, value: u8) -> &mut Passgen {
+        self.min_strength_score = value;
+        self
+    }
+
+    /// Set value of the field `max_attempts` for `Passgen`. It will not be less than 1.
+    pub fn set_max_attempts(&mut self, value: u32) -> &mut Passgen {
+        self.max_attempts = value.max(1);
+        self
+    }
+
     /// Set password for validation and strength checking.
     /// This method is useful when you want to validate or check the strength
     /// of an existing password.
+    ///
+    /// The password is normalized according to the `input_policy`.
     pub fn set_password(&mut self, password: &str) -> &mut Passgen {
-        self.password = password.to_string();
+        self.password = self.input_policy.normalize(password);
         self
     }
 
@@ -250,202 +674,211 @@ impl Passgen {
         self
     }
 
-    /// Generate result. Argument "length" will not be less than 4.
-    /// The generated password is automatically stored in the `password` field
-    /// for immediate validation or strength checking.
-    pub fn generate(&mut self, length: u32) -> String {
-        if !self.is_ruleset_clean() {
-            let res_len = if length < 4 { 4 } else { length };
-
-            let mut pwd = self.generate_pass(res_len);
-
-            if self.custom_charset.len() == 0 {
-                while !self.validate_password_rules(pwd.clone()) {
-                    pwd = self.generate_pass(res_len);
-                }
+    /// Add organization specific words to the `blocklist`.
+    /// Empty words are skipped.
+    pub fn add_blocklist_words(&mut self, words: &[&str]) -> &mut Passgen {
+        for word in words {
+            let word = fold_case(word.trim());
+            if !word.is_empty() && !self.blocklist.contains(&word) {
+                self.blocklist.push(word);
             }
-
-            self.password = pwd.clone();
-            pwd
-        } else {
-            self.password.clear();
-            "".to_string()
         }
+        self
     }
 
-    /// Validate if the current password matches the configured rules.
-    pub fn validate_password(&self) -> bool {
-        if self.password.is_empty() {
-            return false;
-        }
-
-        if self.custom_charset.len() > 0 {
-            // If custom charset is set, check if all characters are from that charset
-            for ch in self.password.chars() {
-                if !self.custom_charset.contains(ch) {
-                    return false;
-                }
-            }
-            return true;
-        }
-
-        self.validate_password_rules(self.password.clone())
+    /// Add words to the `blocklist` from a file with one word per line.
+    /// Empty lines and lines starting with `#` are skipped.
+    pub fn load_blocklist_file<P: AsRef<Path>>(&mut self, path: P) -> io::Result<&mut Passgen> {
+        let content = fs::read_to_string(path)?;
+        let words: Vec<&str> = content
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .collect();
+        Ok(self.add_blocklist_words(&words))
     }
 
-    /// Calculate password strength score (0-100).
-    /// Based on multiple factors: length, character variety, entropy, and common patterns.
-    pub fn password_strength_score(&self) -> u8 {
-        if self.password.is_empty() {
-            return 0;
-        }
+    /// Set value of the field `enab_blocklist_validation` for `Passgen`.
+    pub fn set_blocklist_validation(&mut self, value: bool) -> &mut Passgen {
+        self.enab_blocklist_validation = value;
+        self
+    }
 
-        let password = &self.password;
-        let length = password.len();
+    /// Set which per-language dictionaries of common passwords are consulted by the strength scoring.
+    pub fn set_dictionary_scope(&mut self, scope: DictionaryScope) -> &mut Passgen {
+        self.dictionary_scope = scope;
+        self
+    }
 
-        // Very short passwords get 0
-        if length < 4 {
-            return 0;
-        }
+    /// Set normalization and allowed characters policy for the passwords
+    /// set with `set_password()`. The current password is normalized too.
+    ///
+    /// Use `InputPolicy::nist()` for the NIST SP 800-63B recommendations.
+    pub fn set_input_policy(&mut self, policy: InputPolicy) -> &mut Passgen {
+        self.input_policy = policy;
+        self.password = self.input_policy.normalize(&self.password);
+        self
+    }
 
-        let mut score = 0i32;
-
-        // 1. Length score (max 25 points)
-        score += match length {
-            0..=4 => 0,
-            5..=6 => 5,
-            7..=8 => 10,
-            9..=10 => 15,
-            11..=12 => 20,
-            _ => 25,
-        };
-
-        // 2. Character variety analysis
-        let mut has_lowercase = false;
-        let mut has_uppercase = false;
-        let mut has_digits = false;
-        let mut has_special = false;
-        let mut unique_chars = std::collections::HashSet::new();
-
-        for ch in password.chars() {
-            unique_chars.insert(ch);
-
-            if ch.is_ascii_lowercase() {
-                has_lowercase = true;
-            } else if ch.is_ascii_uppercase() {
-                has_uppercase = true;
-            } else if ch.is_ascii_digit() {
-                has_digits = true;
-            } else if ch.is_ascii_punctuation() || "[]{}()<>".contains(ch) {
-                has_special = true;
+    /// Generate result. Argument "length" will not be less than 4.
+    /// The generated password is automatically stored in the `password` field
+    /// for immediate validation or strength checking.
+    /// Returns an empty string if the `min_strength_score` can't be reached, see `try_generate()`.
+    pub fn generate(&mut self, length: u32) -> String {
+        match self.try_generate(length) {
+            Ok(pwd) => pwd,
+            Err(_) => {
+                self.password.clear();
+                "".to_string()
             }
         }
+    }
 
-        // Count character types
-        let char_type_count = [has_lowercase, has_uppercase, has_digits, has_special]
+    /// Generate result like `generate()`, but report why nothing was generated.
+    /// Positions without characters left by the rules and a `group_separator` unsafe
+    /// in the `safe_contexts` are reported before any attempt.
+    /// Results are generated until one satisfies the rules and reaches the `min_strength_score`,
+    /// but no more than `max_attempts` times.
+    pub fn try_generate(&mut self, length: u32) -> Result<String, GenerationError> {
+        if self.is_ruleset_clean() {
+            self.password.clear();
+            return Err(GenerationError::CleanRuleset);
+        }
+        if self.group_size > 0
+            && !self
+                .safe_contexts
+                .iter()
+                .all(|context| context.is_safe(self.group_separator))
+        {
+            self.password.clear();
+            return Err(GenerationError::UnsafeSeparator);
+        }
+        let res_len = if length < 4 { 4 } else { length };
+        if let Some(index) = self
+            .position_pools(res_len)
             .iter()
-            .filter(|&&x| x)
-            .count();
-
-        // Character variety score (max 25 points)
-        let mut variety_score = 0;
-        if has_lowercase { variety_score += 5; }
-        if has_uppercase { variety_score += 5; }
-        if has_digits { variety_score += 5; }
-        if has_special { variety_score += 10; }
-
-        // Bonus for multiple character types
-        match char_type_count {
-            2 => variety_score += 5,
-            3 => variety_score += 10,
-            4 => variety_score += 15,
-            _ => {}
+            .position(|pool| pool.is_empty())
+        {
+            self.password.clear();
+            return Err(GenerationError::EmptyPosition {
+                index: index as u32,
+                length: res_len,
+            });
         }
+        let attempts = self.max_attempts.max(1);
+        let mut rules_satisfied = false;
 
-        // Cap variety score at 25
-        score += variety_score.min(25);
+        for _ in 0..attempts {
+            let pwd = match self.generate_pass(res_len) {
+                Some(pwd) => pwd,
+                None => continue,
+            };
 
-        // 3. Unique characters ratio (max 20 points)
-        let uniqueness_ratio = unique_chars.len() as f32 / length as f32;
-        score += (uniqueness_ratio * 20.0) as i32;
-
-        // 4. Check for exact matches with weak passwords (immediate 0)
-        let weak_passwords = [
-            "password", "123456", "qwerty", "admin", "welcome",
-            "12345678", "123456789", "12345", "1234", "111111",
-        ];
+            if self.custom_charset.is_empty() && !self.validate_password_rules(pwd.clone()) {
+                continue;
+            }
+            rules_satisfied = true;
 
-        let lower_pwd = password.to_lowercase();
-        for weak in &weak_passwords {
-            if lower_pwd == *weak {
-                return 0;
+            self.password = pwd.clone();
+            if self.min_strength_score == 0
+                || self.password_strength_score() >= self.min_strength_score
+            {
+                self.password = format_groups(&pwd, self.group_size, self.group_separator);
+                return Ok(self.password.clone());
             }
         }
 
-        // 5. Check for containing weak patterns
-        let weak_patterns = ["password", "123", "qwerty", "admin", "letmein"];
-
-        let mut pattern_penalty = 0;
-        for pattern in &weak_patterns {
-            if lower_pwd.contains(pattern) {
-                pattern_penalty += 15;
-            }
+        self.password.clear();
+        if rules_satisfied {
+            Err(GenerationError::StrengthNotReached {
+                min_score: self.min_strength_score,
+                length: res_len,
+                attempts,
+            })
+        } else {
+            Err(GenerationError::RulesNotSatisfied {
+                length: res_len,
+                attempts,
+            })
         }
+    }
 
-        // 6. Penalties for weak patterns
-        let mut penalty = pattern_penalty;
-        let chars: Vec<char> = password.chars().collect();
+    /// Generate result with the length drawn from the range `min..=max`
+    /// according to the `length_distribution`. Argument "min" will not be less than 4.
+    /// The generated password is automatically stored in the `password` field.
+    pub fn generate_range(&mut self, min: u32, max: u32) -> String {
+        let mut rng = new_rng();
+        let draw: f64 = rng.random();
 
-        // Check for sequential characters
-        for i in 0..chars.len().saturating_sub(2) {
-            let c1 = chars[i] as u32;
-            let c2 = chars[i + 1] as u32;
-            let c3 = chars[i + 2] as u32;
+        let mut cumulative = 0.0;
+        let probabilities = self.length_probabilities(min, max);
+        let length = probabilities
+            .iter()
+            .find(|(_, probability)| {
+                cumulative += probability;
+                draw < cumulative
+            })
+            .or(probabilities.last())
+            .map(|&(length, _)| length)
+            .unwrap_or(min);
+
+        self.generate(length)
+    }
 
-            if c2 == c1 + 1 && c3 == c2 + 1 {
-                penalty += 10;
-                break;
-            }
+    /// Validate if the current password matches the configured rules.
+    /// Characters not allowed by the `input_policy` fail the validation.
+    /// Words from the `blocklist` fail the validation if `enab_blocklist_validation` is on.
+    /// Repeated and sequential characters fail the validation under the rules
+    /// `enab_unique_chars`, `max_consecutive_chars` and `enab_no_sequences`
+    /// and characters at the wrong positions fail it under the *"Strong & usability"* rule
+    /// and the `position_constraints`, the same way as they are drawn by `generate()`.
+    /// Characters which need escaping in the `safe_contexts` fail the validation.
+    /// Separators of the groups of the `group_size` are stripped out before the validation,
+    /// except for the check of the `safe_contexts`.
+    ///
+    /// ⚠️Since 2.0.0 the positions are checked too, so passwords accepted by 1.x may fail,
+    /// like the ones of the *"Strong & usability"* rule which don't end with a symbol.
+    pub fn validate_password(&self) -> bool {
+        let password = self.ungrouped_password();
+        if password.is_empty() || !self.input_policy.is_allowed(&password) {
+            return false;
         }
 
-        // Check for repeated characters
-        for i in 0..chars.len().saturating_sub(2) {
-            if chars[i] == chars[i + 1] && chars[i] == chars[i + 2] {
-                penalty += 10;
-                break;
-            }
+        // The separators are pasted with the groups too
+        if !self
+            .safe_contexts
+            .iter()
+            .all(|context| context.is_safe(&self.password))
+        {
+            return false;
         }
 
-        // Penalty for too few character types
-        if char_type_count < 2 {
-            penalty += 10;
+        if self.enab_blocklist_validation && !self.find_blocklist_words(&password).is_empty() {
+            return false;
         }
 
-        // Apply penalty (max 30 points penalty)
-        score -= penalty.min(30);
-
-        // 7. Simple entropy estimation (max 10 points)
-        // This is a simplified calculation to avoid over-scoring
-        let mut charset_size = 0;
-        if has_lowercase { charset_size += 26; }
-        if has_uppercase { charset_size += 26; }
-        if has_digits { charset_size += 10; }
-        if has_special { charset_size += 32; }
-
-        if charset_size > 0 {
-            // Very conservative entropy calculation
-            // We cap it at 10 points to avoid over-scoring
-            let entropy_per_char = (charset_size as f32).log2();
-            let total_entropy = length as f32 * entropy_per_char;
-
-            // Normalize to 0-10 points (very conservative)
-            let entropy_score = (total_entropy / 10.0).min(10.0);
-            score += entropy_score as i32;
+        // Every character must be one `generate` could draw at its position
+        if !self.validate_char_rules(&password) || !self.validate_position_pools(&password) {
+            return false;
         }
 
-        // Ensure score is between 0 and 100
-        score = score.max(0).min(100);
+        if !self.custom_charset.is_empty() {
+            return true;
+        }
 
-        score as u8
+        self.validate_password_rules(password)
+    }
+
+    /// Calculate password strength score (0-100).
+    /// Based on multiple factors: length, character variety, entropy, and common patterns.
+    /// Length is measured in Unicode characters and letters of any script are recognized.
+    ///
+    /// Use [Passgen::password_strength_report] to get the breakdown of the score.
+    pub fn password_strength_score(&self) -> u8 {
+        if self.password.is_empty() {
+            return 0;
+        }
+
+        self.password_strength_report().score
     }
 
     /// Get password strength level description in the selected language.
@@ -460,43 +893,14 @@ impl Passgen {
             && !self.enab_num
             && !self.enab_spec_symbs
             && !self.enab_strong_usab
-            && self.custom_charset.len() == 0
+            && self.custom_charset.is_empty()
     }
 
     fn validate_password_rules(&self, pass: String) -> bool {
-        let check_to_available_for = |symbols: &str| -> bool {
-            let mut res = false;
-            for ch in pass.chars() {
-                if symbols.contains(ch) {
-                    res = true;
-                    break;
-                }
-            }
-            res
-        };
-
         // compliance check
-        if self.enab_letters || self.enab_strong_usab {
-            if !check_to_available_for(LETTERS_CHARSET) {
-                return false;
-            }
-        }
-        if self.enab_u_letters || self.enab_strong_usab {
-            if !check_to_available_for(U_LETTERS_CHARSET) {
-                return false;
-            }
-        }
-        if self.enab_num || self.enab_strong_usab {
-            if !check_to_available_for(NUM_CHARSET) {
-                return false;
-            }
-        }
-        if self.enab_spec_symbs || self.enab_strong_usab {
-            if !check_to_available_for(SPEC_SYMB_CHARSET) {
-                return false;
-            }
-        }
-        true
+        self.required_charsets()
+            .iter()
+            .all(|symbols| pass.chars().any(|ch| symbols.contains(ch)))
     }
 }
 
@@ -0,0 +1,147 @@
+pub mod recovery {
+    use crate::Passgen;
+    use crate::gen_engine::gen_engine::new_rng;
+    use rand::Rng;
+    use std::collections::HashSet;
+
+    // Lowercase letters and numbers without ambiguous `0`, `1`, `i`, `l` and `o`.
+    pub const RECOVERY_CODE_ALPHABET: &str = "abcdefghjkmnpqrstuvwxyz23456789";
+    // Groups of characters typed the same by mistake.
+    const LOOKALIKES: [&str; 2] = ["0oO", "1lIi|"];
+
+    /// Format of the recovery codes, like `k7fq-9xmd`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct RecoveryCodeFormat {
+        /// Characters of the codes.
+        pub alphabet: &'static str,
+        /// Number of the groups of characters.
+        pub groups: u32,
+        /// Number of characters in a group.
+        pub group_size: u32,
+        /// Separator between the groups.
+        pub separator: &'static str,
+    }
+
+    impl Default for RecoveryCodeFormat {
+        fn default() -> RecoveryCodeFormat {
+            RecoveryCodeFormat {
+                alphabet: RECOVERY_CODE_ALPHABET,
+                groups: 2,
+                group_size: 4,
+                separator: "-",
+            }
+        }
+    }
+
+    impl RecoveryCodeFormat {
+        /// Get the entropy in bits of a single code of the format.
+        pub fn entropy_bits(&self) -> f64 {
+            let alphabet: HashSet<char> = self.alphabet.chars().collect();
+            (self.groups * self.group_size) as f64 * (alphabet.len() as f64).log2()
+        }
+
+        /// Normalize the code entered by a user: separators, dashes and whitespace are removed,
+        /// the case and the lookalike characters, like `O` for `0`,
+        /// are changed to the ones of the alphabet.
+        pub fn normalize(&self, input: &str) -> String {
+            input
+                .chars()
+                .filter(|&ch| {
+                    self.alphabet.contains(ch)
+                        || !(ch.is_whitespace() || ch == '-' || self.separator.contains(ch))
+                })
+                .map(|ch| self.alphabet_char(ch))
+                .collect()
+        }
+
+        // Get the character of the alphabet the character stands for.
+        fn alphabet_char(&self, ch: char) -> char {
+            if self.alphabet.contains(ch) {
+                return ch;
+            }
+            for other_case in [ch.to_ascii_lowercase(), ch.to_ascii_uppercase()] {
+                if self.alphabet.contains(other_case) {
+                    return other_case;
+                }
+            }
+            let mut lookalikes = LOOKALIKES
+                .iter()
+                .filter(|group| group.contains(ch))
+                .flat_map(|group| group.chars())
+                .filter(|&lookalike| self.alphabet.contains(lookalike));
+            match (lookalikes.next(), lookalikes.next()) {
+                (Some(lookalike), None) => lookalike,
+                _ => ch,
+            }
+        }
+    }
+
+    impl Passgen {
+        /// Generate a set of unique one-time recovery codes of the format.
+        ///
+        /// If the format has fewer possible codes than requested, all of them are returned.
+        pub fn generate_recovery_codes(
+            &mut self,
+            count: usize,
+            format: &RecoveryCodeFormat,
+        ) -> Vec<String> {
+            let alphabet: Vec<char> = format.alphabet.chars().collect();
+            let unique: HashSet<char> = alphabet.iter().copied().collect();
+            let length = format.groups * format.group_size;
+            let possible = (unique.len() as u128).saturating_pow(length);
+            let count = count.min(possible.min(usize::MAX as u128) as usize);
+
+            let mut rng = new_rng();
+            let mut seen: HashSet<String> = HashSet::new();
+            let mut codes = Vec::with_capacity(count);
+            while codes.len() < count {
+                let groups: Vec<String> = (0..format.groups)
+                    .map(|_| {
+                        (0..format.group_size)
+                            .map(|_| alphabet[rng.random_range(0..alphabet.len())])
+                            .collect()
+                    })
+                    .collect();
+                let code = groups.join(format.separator);
+                if seen.insert(format.normalize(&code)) {
+                    codes.push(code);
+                }
+            }
+            codes
+        }
+    }
+
+    /// Check if the code entered by a user is the recovery code.
+    /// Both are normalized and compared in constant time.
+    pub fn verify_recovery_code(input: &str, code: &str, format: &RecoveryCodeFormat) -> bool {
+        constant_time_eq(
+            format.normalize(input).as_bytes(),
+            format.normalize(code).as_bytes(),
+        )
+    }
+
+    /// Find the code entered by a user in the set of the recovery codes.
+    /// Every code is compared in constant time, so the position of the match doesn't leak.
+    /// Returns the index of the matched code, which should be removed from the set after use.
+    pub fn find_recovery_code(
+        input: &str,
+        codes: &[String],
+        format: &RecoveryCodeFormat,
+    ) -> Option<usize> {
+        let mut found = None;
+        for (index, code) in codes.iter().enumerate() {
+            if verify_recovery_code(input, code, format) && found.is_none() {
+                found = Some(index);
+            }
+        }
+        found
+    }
+
+    // Compare the bytes without stopping at the first difference.
+    fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+        if a.len() != b.len() {
+            return false;
+        }
+        a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+    }
+}
@@ -0,0 +1,876 @@
+pub mod strength {
+    use crate::Passgen;
+    use crate::dictionary::dictionary::{DictionaryScope, enabled_languages, words};
+    use crate::input_policy::input_policy::is_ignorable;
+    use crate::keyboard::keyboard::{KEYBOARD_LAYOUTS, KeyboardLayout, find_keyboard_walks};
+    use crate::lang::lang::{Language, StrengthTranslations, SuggestionTranslations};
+    use std::collections::HashSet;
+
+    // Exact matches with these passwords get a score of 0.
+    pub const WEAK_PASSWORDS: [&str; 10] = [
+        "password",
+        "123456",
+        "qwerty",
+        "admin",
+        "welcome",
+        "12345678",
+        "123456789",
+        "12345",
+        "1234",
+        "111111",
+    ];
+    // Each of these patterns found in a password is penalized.
+    pub const WEAK_PATTERNS: [&str; 5] = ["password", "123", "qwerty", "admin", "letmein"];
+    // Common leetspeak substitutions and the letters they stand for.
+    pub const LEET_SUBSTITUTIONS: [(char, &str); 9] = [
+        ('@', "a"),
+        ('4', "a"),
+        ('0', "o"),
+        ('3', "e"),
+        ('$', "s"),
+        ('5', "s"),
+        ('1', "il"),
+        ('!', "i"),
+        ('7', "t"),
+    ];
+    // Limit of the de-leeted forms checked for a single password.
+    const MAX_LEET_VARIANTS: usize = 256;
+    // Common English words checked for being typed on a non-Latin layout.
+    pub const ENGLISH_WORDS: [&str; 12] = [
+        "password", "qwerty", "admin", "welcome", "letmein", "dragon", "monkey", "master",
+        "sunshine", "iloveyou", "football", "princess",
+    ];
+    // Common Russian words checked for being typed on the English layout.
+    pub const RUSSIAN_WORDS: [&str; 20] = [
+        "пароль",
+        "привет",
+        "любовь",
+        "солнце",
+        "солнышко",
+        "котик",
+        "зайка",
+        "рыбка",
+        "наташа",
+        "максим",
+        "машина",
+        "россия",
+        "москва",
+        "свобода",
+        "секрет",
+        "ангел",
+        "танюша",
+        "люблю",
+        "здравствуй",
+        "спартак",
+    ];
+    // Words checked for being typed on a wrong layout even without the dictionaries.
+    const LAYOUT_WORDS: [(Language, &[&str]); 2] = [
+        (Language::English, &ENGLISH_WORDS),
+        (Language::Russian, &RUSSIAN_WORDS),
+    ];
+    // Maximum score of a leetspeak variant of a weak password.
+    pub const LEET_PASSWORD_MAX_SCORE: u8 = 10;
+    // Passwords shorter than this get a suggestion to use a longer one.
+    pub const SUGGESTED_MIN_LENGTH: usize = 12;
+    // Only the first characters of longer passwords are scored, so the time of the scoring
+    // is bounded for any input. Longer passwords get the full length score anyway.
+    pub const MAX_SCORED_LENGTH: usize = 256;
+    // Minimal score which is not described as "Weak" or "Very Weak" by the strength level.
+    pub const NOT_WEAK_MIN_SCORE: u8 = 41;
+
+    /// Kind of a weak pattern detected in a password.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum PatternKind {
+        /// The whole password is a well-known weak password. Forces the score to 0.
+        CommonPassword,
+        /// The password contains a well-known weak pattern.
+        WeakPattern,
+        /// The whole password is a leetspeak variant of a well-known weak password,
+        /// like `p@$$w0rd`. Limits the score to [LEET_PASSWORD_MAX_SCORE].
+        LeetCommonPassword,
+        /// The password contains a leetspeak variant of a well-known weak pattern,
+        /// like `P@ssw0rd123`.
+        LeetPattern,
+        /// Three or more characters with ascending codes, like `abc` or `123`.
+        Sequence,
+        /// Three or more characters with descending codes, like `cba` or `987`.
+        DescendingSequence,
+        /// Characters typed by neighbouring keys on the keyboard layout,
+        /// like `qwerty`, `asdf` or `1qaz`.
+        KeyboardWalk(KeyboardLayout),
+        /// The same character three or more times in a row, like `aaa`.
+        Repeat,
+        /// A date or a year, like `1987`, `12.05.1990` or `050190`.
+        Date,
+        /// A word from the organization `blocklist` or its leetspeak variant.
+        Blocklisted,
+        /// A common password or word from the per-language dictionary,
+        /// or its leetspeak variant.
+        DictionaryWord(Language),
+        /// A word of the language typed on a wrong keyboard layout,
+        /// like Russian `пароль` typed on the English layout as `gfhjkm`.
+        WrongLayout(Language),
+        /// Less than two character types are used.
+        FewCharTypes,
+    }
+
+    /// A weak pattern detected in a password and the penalty it gave.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct PatternMatch {
+        /// What kind of pattern was found.
+        pub kind: PatternKind,
+        /// The part of the password that matched. Empty for [PatternKind::FewCharTypes].
+        pub matched: String,
+        /// Points this pattern subtracts before the penalty cap is applied.
+        pub penalty: i32,
+    }
+
+    /// Actionable suggestion for improving a weak password.
+    ///
+    /// Use [SuggestionTranslations::get_text] to get the text in the selected language.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[non_exhaustive]
+    pub enum Suggestion {
+        /// The password is shorter than [SUGGESTED_MIN_LENGTH] characters.
+        UseLongerPassword,
+        /// There are no lowercase letters.
+        AddLowercase,
+        /// There are no capital letters.
+        AddUppercase,
+        /// There are no digits.
+        AddDigit,
+        /// There are no special characters.
+        AddSymbol,
+        /// Sequential characters were found.
+        AvoidSequences,
+        /// Keyboard walks were found.
+        AvoidKeyboardPatterns,
+        /// Dates or years were found.
+        AvoidDates,
+        /// Triple repeated characters were found.
+        AvoidRepeats,
+        /// A weak password or a weak pattern was found.
+        AvoidCommonWords,
+    }
+
+    /// Structured breakdown of [Passgen::password_strength_score].
+    ///
+    /// Every component keeps the points it contributed to the final score,
+    /// so it is possible to explain why a password got its score.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct StrengthReport {
+        /// Password length in Unicode characters, no more than [MAX_SCORED_LENGTH].
+        pub length: usize,
+        /// Points for the length (max 25).
+        pub length_score: i32,
+        /// Presence of lowercase letters.
+        pub has_lowercase: bool,
+        /// Presence of capital letters.
+        pub has_uppercase: bool,
+        /// Presence of letters from scripts without case, like Chinese or Arabic.
+        /// They are counted in the same character type as lowercase letters.
+        pub has_caseless_letters: bool,
+        /// Presence of digits.
+        pub has_digits: bool,
+        /// Presence of special characters.
+        pub has_special: bool,
+        /// Number of used character types (0-4).
+        pub char_type_count: usize,
+        /// Points for the character variety (max 25).
+        pub variety_score: i32,
+        /// Number of distinct characters.
+        pub unique_chars: usize,
+        /// Points for the unique characters ratio (max 20).
+        pub uniqueness_score: i32,
+        /// All weak patterns found in the password.
+        pub patterns: Vec<PatternMatch>,
+        /// Points subtracted for the patterns after the cap (max 30).
+        pub penalty: i32,
+        /// Points for the estimated entropy (max 10).
+        pub entropy_score: i32,
+        /// Final score (0-100), the same value as [Passgen::password_strength_score] returns.
+        pub score: u8,
+        /// Strength level description in the selected language.
+        pub level: &'static str,
+        /// Suggestions for improving the password, most important first.
+        pub suggestions: Vec<Suggestion>,
+    }
+
+    impl StrengthReport {
+        /// Check if a pattern of the given kind was detected.
+        pub fn has_pattern(&self, kind: &PatternKind) -> bool {
+            self.patterns.iter().any(|p| &p.kind == kind)
+        }
+
+        fn collect_suggestions(&self) -> Vec<Suggestion> {
+            let mut suggestions = Vec::new();
+
+            if self.length < SUGGESTED_MIN_LENGTH {
+                suggestions.push(Suggestion::UseLongerPassword);
+            }
+            if !self.has_lowercase && !self.has_caseless_letters {
+                suggestions.push(Suggestion::AddLowercase);
+            }
+            // Capital letters can't be suggested for a script without case
+            if !self.has_uppercase && (self.has_lowercase || !self.has_caseless_letters) {
+                suggestions.push(Suggestion::AddUppercase);
+            }
+            if !self.has_digits {
+                suggestions.push(Suggestion::AddDigit);
+            }
+            if !self.has_special {
+                suggestions.push(Suggestion::AddSymbol);
+            }
+
+            for pattern in &self.patterns {
+                let suggestion = match pattern.kind {
+                    PatternKind::CommonPassword
+                    | PatternKind::WeakPattern
+                    | PatternKind::LeetCommonPassword
+                    | PatternKind::LeetPattern
+                    | PatternKind::Blocklisted
+                    | PatternKind::DictionaryWord(_)
+                    | PatternKind::WrongLayout(_) => Suggestion::AvoidCommonWords,
+                    PatternKind::Sequence | PatternKind::DescendingSequence => {
+                        Suggestion::AvoidSequences
+                    }
+                    PatternKind::KeyboardWalk(_) => Suggestion::AvoidKeyboardPatterns,
+                    PatternKind::Date => Suggestion::AvoidDates,
+                    PatternKind::Repeat => Suggestion::AvoidRepeats,
+                    PatternKind::FewCharTypes => continue,
+                };
+                if !suggestions.contains(&suggestion) {
+                    suggestions.push(suggestion);
+                }
+            }
+
+            suggestions
+        }
+    }
+
+    impl Passgen {
+        /// Get structured password strength breakdown.
+        /// Contains every component of the score with its contribution,
+        /// detected weak patterns, the final score and the strength level.
+        /// Only the first [MAX_SCORED_LENGTH] characters are scored.
+        pub fn password_strength_report(&self) -> StrengthReport {
+            // Invisible characters like a zero-width joiner are not counted,
+            // neither are the separators of the groups, like in `validate_password`
+            let password: String = self
+                .ungrouped_password()
+                .chars()
+                .filter(|&ch| !is_ignorable(ch))
+                .take(MAX_SCORED_LENGTH)
+                .collect();
+            let password = &password;
+            // Length in characters, not bytes
+            let length = password.chars().count();
+
+            // 1. Length score (max 25 points)
+            let length_score = match length {
+                0..=4 => 0,
+                5..=6 => 5,
+                7..=8 => 10,
+                9..=10 => 15,
+                11..=12 => 20,
+                _ => 25,
+            };
+
+            // 2. Character variety analysis
+            // Letters of any script are counted, case is taken into account
+            // only for the scripts which have it.
+            let mut has_lowercase = false;
+            let mut has_uppercase = false;
+            let mut has_caseless_letters = false;
+            let mut has_digits = false;
+            let mut has_special = false;
+            let mut unique_chars = HashSet::new();
+
+            for ch in password.chars() {
+                unique_chars.insert(ch);
+
+                if ch.is_lowercase() {
+                    has_lowercase = true;
+                } else if ch.is_uppercase() {
+                    has_uppercase = true;
+                } else if ch.is_alphabetic() {
+                    has_caseless_letters = true;
+                } else if ch.is_numeric() {
+                    has_digits = true;
+                } else if !ch.is_whitespace() && !ch.is_control() {
+                    has_special = true;
+                }
+            }
+
+            // Caseless letters take the place of lowercase letters
+            let has_letters = has_lowercase || has_caseless_letters;
+
+            // Count character types
+            let char_type_count = [has_letters, has_uppercase, has_digits, has_special]
+                .iter()
+                .filter(|&&x| x)
+                .count();
+
+            // Character variety score (max 25 points)
+            let mut variety_score = 0;
+            if has_letters {
+                variety_score += 5;
+            }
+            if has_uppercase {
+                variety_score += 5;
+            }
+            if has_digits {
+                variety_score += 5;
+            }
+            if has_special {
+                variety_score += 10;
+            }
+
+            // Bonus for multiple character types
+            match char_type_count {
+                2 => variety_score += 5,
+                3 => variety_score += 10,
+                4 => variety_score += 15,
+                _ => {}
+            }
+
+            // Cap variety score at 25
+            let variety_score = variety_score.min(25);
+
+            // 3. Unique characters ratio (max 20 points)
+            let uniqueness_score = if length > 0 {
+                (unique_chars.len() as f32 / length as f32 * 20.0) as i32
+            } else {
+                0
+            };
+
+            let mut patterns = Vec::new();
+
+            // 4. Check for exact matches with weak passwords (score 0)
+            let lower_pwd = fold_case(password);
+            if let Some(weak) = WEAK_PASSWORDS.iter().find(|&&weak| lower_pwd == weak) {
+                patterns.push(PatternMatch {
+                    kind: PatternKind::CommonPassword,
+                    matched: weak.to_string(),
+                    penalty: 0,
+                });
+            }
+
+            // 5. Check for containing weak patterns
+            for pattern in &WEAK_PATTERNS {
+                if lower_pwd.contains(pattern) {
+                    patterns.push(PatternMatch {
+                        kind: PatternKind::WeakPattern,
+                        matched: pattern.to_string(),
+                        penalty: 15,
+                    });
+                }
+            }
+
+            // Check for leetspeak variants of weak passwords and patterns
+            if let Some(weak) = WEAK_PASSWORDS
+                .iter()
+                .find(|&&weak| lower_pwd != weak && is_leet_word(&lower_pwd, weak))
+            {
+                patterns.push(PatternMatch {
+                    kind: PatternKind::LeetCommonPassword,
+                    matched: weak.to_string(),
+                    penalty: 30,
+                });
+            }
+            for pattern in &WEAK_PATTERNS {
+                if !lower_pwd.contains(pattern) && contains_leet_word(&lower_pwd, pattern) {
+                    patterns.push(PatternMatch {
+                        kind: PatternKind::LeetPattern,
+                        matched: pattern.to_string(),
+                        penalty: 12,
+                    });
+                }
+            }
+
+            // Check for organization specific words
+            for word in self.find_blocklist_words(password) {
+                patterns.push(PatternMatch {
+                    kind: PatternKind::Blocklisted,
+                    matched: word,
+                    penalty: 20,
+                });
+            }
+
+            // Check for common passwords and words from the per-language dictionaries,
+            // the whole password being such a word gets more penalty
+            for (language, word, whole) in self.find_dictionary_words(&lower_pwd) {
+                patterns.push(PatternMatch {
+                    kind: PatternKind::DictionaryWord(language),
+                    matched: word,
+                    penalty: if whole { 30 } else { 15 },
+                });
+            }
+
+            // Check for common words typed on a wrong keyboard layout
+            for (language, typed) in find_wrong_layout_words(password, &self.dictionary_languages())
+            {
+                patterns.push(PatternMatch {
+                    kind: PatternKind::WrongLayout(language),
+                    matched: typed,
+                    penalty: 15,
+                });
+            }
+
+            // 6. Penalties for weak patterns
+            let chars: Vec<char> = password.chars().collect();
+
+            // Check for sequential characters
+            if let Some(run) = chars.windows(3).find(|w| {
+                let (c1, c2, c3) = (w[0] as u32, w[1] as u32, w[2] as u32);
+                c2 == c1 + 1 && c3 == c2 + 1
+            }) {
+                patterns.push(PatternMatch {
+                    kind: PatternKind::Sequence,
+                    matched: String::from_iter(run),
+                    penalty: 10,
+                });
+            }
+
+            if let Some(run) = chars.windows(3).find(|w| {
+                let (c1, c2, c3) = (w[0] as u32, w[1] as u32, w[2] as u32);
+                c2 + 1 == c1 && c3 + 1 == c2
+            }) {
+                patterns.push(PatternMatch {
+                    kind: PatternKind::DescendingSequence,
+                    matched: String::from_iter(run),
+                    penalty: 10,
+                });
+            }
+
+            // Check for keyboard walks, longer walks get more penalty
+            for (layout, walk) in find_keyboard_walks(password) {
+                let penalty = (walk.chars().count() as i32 * 3).min(20);
+                patterns.push(PatternMatch {
+                    kind: PatternKind::KeyboardWalk(layout),
+                    matched: walk,
+                    penalty,
+                });
+            }
+
+            // Check for dates, the more of the password a date covers the more penalty
+            for date in find_dates(password) {
+                let covered = date.chars().count() * 20 / length;
+                patterns.push(PatternMatch {
+                    kind: PatternKind::Date,
+                    matched: date,
+                    penalty: 5 + covered as i32,
+                });
+            }
+
+            // Check for repeated characters
+            if let Some(run) = chars.windows(3).find(|w| w[0] == w[1] && w[0] == w[2]) {
+                patterns.push(PatternMatch {
+                    kind: PatternKind::Repeat,
+                    matched: String::from_iter(run),
+                    penalty: 10,
+                });
+            }
+
+            // Penalty for too few character types
+            if char_type_count < 2 {
+                patterns.push(PatternMatch {
+                    kind: PatternKind::FewCharTypes,
+                    matched: String::new(),
+                    penalty: 10,
+                });
+            }
+
+            // Apply penalty (max 30 points penalty)
+            let penalty = patterns.iter().map(|p| p.penalty).sum::<i32>().min(30);
+
+            // 7. Simple entropy estimation (max 10 points)
+            // This is a simplified calculation to avoid over-scoring
+            let mut charset_size = 0;
+            if has_letters {
+                charset_size += 26;
+            }
+            if has_uppercase {
+                charset_size += 26;
+            }
+            if has_digits {
+                charset_size += 10;
+            }
+            if has_special {
+                charset_size += 32;
+            }
+
+            let mut entropy_score = 0;
+            if charset_size > 0 {
+                // Very conservative entropy calculation
+                // We cap it at 10 points to avoid over-scoring
+                let entropy_per_char = (charset_size as f32).log2();
+                let total_entropy = length as f32 * entropy_per_char;
+
+                // Normalize to 0-10 points (very conservative)
+                entropy_score = (total_entropy / 10.0).min(10.0) as i32;
+            }
+
+            // Very short passwords and exact weak matches get 0
+            let score = if length < 4
+                || patterns
+                    .iter()
+                    .any(|p| p.kind == PatternKind::CommonPassword)
+            {
+                0
+            } else {
+                // Ensure score is between 0 and 100
+                let score = (length_score + variety_score + uniqueness_score - penalty
+                    + entropy_score)
+                    .clamp(0, 100) as u8;
+                // Leetspeak variants of weak passwords are almost as weak as the originals
+                if patterns
+                    .iter()
+                    .any(|p| p.kind == PatternKind::LeetCommonPassword)
+                {
+                    score.min(LEET_PASSWORD_MAX_SCORE)
+                } else {
+                    score
+                }
+            };
+
+            let mut report = StrengthReport {
+                length,
+                length_score,
+                has_lowercase,
+                has_uppercase,
+                has_caseless_letters,
+                has_digits,
+                has_special,
+                char_type_count,
+                variety_score,
+                unique_chars: unique_chars.len(),
+                uniqueness_score,
+                patterns,
+                penalty,
+                entropy_score,
+                score,
+                level: StrengthTranslations::get_level(self.language, score),
+                suggestions: Vec::new(),
+            };
+            report.suggestions = report.collect_suggestions();
+            report
+        }
+
+        /// Find words from the `blocklist` in the password, including case and leetspeak variants.
+        pub fn find_blocklist_words(&self, password: &str) -> Vec<String> {
+            if self.blocklist.is_empty() {
+                return Vec::new();
+            }
+            let password = fold_case(password);
+            self.blocklist
+                .iter()
+                .filter(|word| contains_leet_word(&password, word))
+                .cloned()
+                .collect()
+        }
+
+        /// Find common passwords and words from the per-language dictionaries
+        /// in the case folded password, including leetspeak variants.
+        ///
+        /// Returns the language, the word and whether the word is the whole password.
+        fn find_dictionary_words(&self, lower_pwd: &str) -> Vec<(Language, String, bool)> {
+            let languages = self.dictionary_languages();
+            let mut found: Vec<(Language, String, bool)> = Vec::new();
+
+            for language in languages {
+                for word in words(language) {
+                    let word = fold_case(word);
+                    // Skip the words which are a part of an already found one
+                    if found.iter().any(|(_, f, _)| f.contains(&word)) {
+                        continue;
+                    }
+                    if contains_leet_word(lower_pwd, &word) {
+                        let whole = is_leet_word(lower_pwd, &word);
+                        found.retain(|(_, f, _)| !word.contains(f.as_str()));
+                        found.push((language, word, whole));
+                    }
+                }
+            }
+
+            found
+        }
+
+        // Languages of the dictionaries consulted under the `dictionary_scope`.
+        fn dictionary_languages(&self) -> Vec<Language> {
+            match self.dictionary_scope {
+                DictionaryScope::Language => vec![self.language],
+                DictionaryScope::AllEnabled => enabled_languages(),
+                DictionaryScope::Disabled => Vec::new(),
+            }
+        }
+
+        /// Get suggestions for improving the current password in the selected language.
+        /// Based on what the strength scoring detects: missing character types,
+        /// sequential and repeated characters, weak patterns and short length.
+        pub fn password_suggestions(&self) -> Vec<&'static str> {
+            self.password_strength_report()
+                .suggestions
+                .into_iter()
+                .map(|s| SuggestionTranslations::get_text(self.language, s))
+                .collect()
+        }
+    }
+
+    /// Fold the case of a string for caseless comparison.
+    ///
+    /// Full lowercase mapping plus the special case foldings which expand or merge
+    /// characters, like German `ß` to `ss` or Greek final `ς` to `σ`.
+    pub fn fold_case(value: &str) -> String {
+        let mut folded = String::with_capacity(value.len());
+        for ch in value.chars().flat_map(char::to_lowercase) {
+            match ch {
+                'ß' => folded.push_str("ss"),
+                'ς' => folded.push('σ'),
+                'ſ' => folded.push('s'),
+                'ﬀ' => folded.push_str("ff"),
+                'ﬁ' => folded.push_str("fi"),
+                'ﬂ' => folded.push_str("fl"),
+                'ﬃ' => folded.push_str("ffi"),
+                'ﬄ' => folded.push_str("ffl"),
+                'ﬅ' | 'ﬆ' => folded.push_str("st"),
+                _ => folded.push(ch),
+            }
+        }
+        folded
+    }
+
+    /// Find common words typed on a wrong keyboard layout,
+    /// like Russian `пароль` typed on the English layout as `gfhjkm`.
+    ///
+    /// Every layout of [KEYBOARD_LAYOUTS] is checked for the words of its language
+    /// typed on any of the other layouts: the built-in common English and Russian words
+    /// and the enabled dictionaries of the languages, like `dict-german` for QWERTZ.
+    ///
+    /// Returns the language of the word and the part of the password it was typed as.
+    pub fn find_wrong_layout_words(
+        password: &str,
+        languages: &[Language],
+    ) -> Vec<(Language, String)> {
+        let chars: Vec<char> = password.chars().collect();
+        let lower: Vec<char> = chars
+            .iter()
+            .map(|&ch| ch.to_lowercase().next().unwrap_or(ch))
+            .collect();
+        let mut found: Vec<(Language, String)> = Vec::new();
+
+        for word_layout in KEYBOARD_LAYOUTS {
+            let language = word_layout.language();
+            let mut layout_words: Vec<Vec<char>> = LAYOUT_WORDS
+                .iter()
+                .filter(|(words_language, _)| *words_language == language)
+                .flat_map(|(_, words)| words.iter().copied())
+                .chain(words(language).filter(|_| languages.contains(&language)))
+                .map(|word| fold_case(word).chars().collect())
+                .filter(|word: &Vec<char>| !word.is_empty() && word.len() <= lower.len())
+                .collect();
+            layout_words.sort();
+            layout_words.dedup();
+
+            for typed_on in KEYBOARD_LAYOUTS {
+                if typed_on == word_layout {
+                    continue;
+                }
+                let converted: Vec<char> = lower
+                    .iter()
+                    .map(|&ch| typed_on.convert_char(ch, word_layout).unwrap_or(ch))
+                    .collect();
+
+                for word in &layout_words {
+                    // The word must be really typed on the other layout
+                    let position = converted
+                        .windows(word.len())
+                        .zip(lower.windows(word.len()))
+                        .position(|(conv, orig)| {
+                            conv == word.as_slice() && orig != word.as_slice()
+                        });
+                    if let Some(i) = position {
+                        let typed = String::from_iter(&chars[i..i + word.len()]);
+                        if !found.contains(&(language, typed.clone())) {
+                            found.push((language, typed));
+                        }
+                    }
+                }
+            }
+        }
+
+        found
+    }
+
+    /// Find dates and years in the password.
+    ///
+    /// Recognized are years from 1900 to 2099 (`1987`), dates with separators
+    /// (`12.05.1990`, `12/05/90`, `1990-05-12`) and dates without them
+    /// (`050190`, `19900512`, `12051990`).
+    pub fn find_dates(password: &str) -> Vec<String> {
+        let chars: Vec<char> = password.chars().collect();
+        let mut dates = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            if !chars[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+
+            // Candidate is a run of digits and date separators between digits
+            let start = i;
+            let mut end = i;
+            while end < chars.len()
+                && (chars[end].is_ascii_digit()
+                    || (".-/".contains(chars[end])
+                        && end + 1 < chars.len()
+                        && chars[end + 1].is_ascii_digit()))
+            {
+                end += 1;
+            }
+            i = end;
+
+            let candidate = String::from_iter(&chars[start..end]);
+            let separators: Vec<char> = candidate
+                .chars()
+                .filter(|ch| !ch.is_ascii_digit())
+                .collect();
+            let groups: Vec<&str> = candidate.split(['.', '-', '/']).collect();
+
+            if groups.len() == 3
+                && separators.iter().all(|&sep| sep == separators[0])
+                && is_separated_date(groups[0], groups[1], groups[2])
+            {
+                dates.push(candidate);
+                continue;
+            }
+
+            for group in groups {
+                if is_compact_date(group) {
+                    dates.push(group.to_string());
+                } else {
+                    // Years inside of a longer run of digits
+                    let digits: Vec<char> = group.chars().collect();
+                    let mut j = 0;
+                    while j + 4 <= digits.len() {
+                        let window = String::from_iter(&digits[j..j + 4]);
+                        if is_year(&window) {
+                            dates.push(window);
+                            j += 4;
+                        } else {
+                            j += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        dates
+    }
+
+    fn is_year(value: &str) -> bool {
+        value.len() == 4 && matches!(value.parse::<u32>(), Ok(1900..=2099))
+    }
+
+    fn is_day_and_month(day: &str, month: &str) -> bool {
+        matches!(day.parse::<u32>(), Ok(1..=31)) && matches!(month.parse::<u32>(), Ok(1..=12))
+    }
+
+    fn is_separated_date(first: &str, second: &str, third: &str) -> bool {
+        let short = |v: &str| (1..=2).contains(&v.len());
+        if is_year(first) && short(second) && short(third) {
+            return is_day_and_month(third, second);
+        }
+        (is_year(third) || third.len() == 2)
+            && short(first)
+            && short(second)
+            && (is_day_and_month(first, second) || is_day_and_month(second, first))
+    }
+
+    fn is_compact_date(digits: &str) -> bool {
+        match digits.len() {
+            8 => {
+                (is_year(&digits[..4]) && is_day_and_month(&digits[6..], &digits[4..6]))
+                    || (is_year(&digits[4..])
+                        && (is_day_and_month(&digits[..2], &digits[2..4])
+                            || is_day_and_month(&digits[2..4], &digits[..2])))
+            }
+            6 => {
+                is_day_and_month(&digits[..2], &digits[2..4])
+                    || is_day_and_month(&digits[2..4], &digits[..2])
+                    || is_day_and_month(&digits[4..], &digits[2..4])
+            }
+            _ => false,
+        }
+    }
+
+    /// Check if the value contains the word, where every character of the value
+    /// is either the character of the word or its leetspeak substitution,
+    /// like `@cme2024` for `acme2024`.
+    pub fn contains_leet_word(value: &str, word: &str) -> bool {
+        let value: Vec<char> = value.chars().collect();
+        let word: Vec<char> = word.chars().collect();
+        if word.is_empty() {
+            return true;
+        }
+        value.windows(word.len()).any(|window| {
+            window.iter().zip(&word).all(|(&ch, &letter)| {
+                ch == letter
+                    || LEET_SUBSTITUTIONS
+                        .iter()
+                        .any(|(leet, letters)| *leet == ch && letters.contains(letter))
+            })
+        })
+    }
+
+    /// Check if the whole value is the word or its leetspeak variant, like `p@ssw0rd`.
+    pub fn is_leet_word(value: &str, word: &str) -> bool {
+        value.chars().count() == word.chars().count() && contains_leet_word(value, word)
+    }
+
+    /// Get all de-leeted forms of the value, like `password` and `passwori`
+    /// for `p@ssw0rd` and `p@ssw0r1`. The value itself is the first form.
+    ///
+    /// Ambiguous substitutions, like `1` for `i` or `l`, give several forms.
+    /// Their number is limited, the first letter is used when the limit is reached.
+    pub fn deleet_candidates(value: &str) -> Vec<String> {
+        let mut candidates = vec![String::new()];
+        let mut substituted = false;
+
+        for ch in value.chars() {
+            let letters = match LEET_SUBSTITUTIONS.iter().find(|(leet, _)| *leet == ch) {
+                Some((_, letters)) => {
+                    substituted = true;
+                    if candidates.len() * letters.len() > MAX_LEET_VARIANTS {
+                        &letters[..1]
+                    } else {
+                        letters
+                    }
+                }
+                None => {
+                    candidates
+                        .iter_mut()
+                        .for_each(|candidate| candidate.push(ch));
+                    continue;
+                }
+            };
+            candidates = candidates
+                .iter()
+                .flat_map(|candidate| {
+                    letters.chars().map(move |letter| {
+                        let mut candidate = candidate.clone();
+                        candidate.push(letter);
+                        candidate
+                    })
+                })
+                .collect();
+        }
+
+        if substituted {
+            candidates.insert(0, value.to_string());
+        }
+        candidates
+    }
+}
@@ -1,5 +1,24 @@
 pub mod tests {
     use crate::Passgen;
+    use crate::api_key::api_key::{ApiKeyFormat, ChecksumAlgorithm, verify_api_key};
+    use crate::check_digit::check_digit::CheckAlgorithm;
+    use crate::context::context::{SAFE_CONTEXTS, SafeContext};
+    use crate::escape::escape::{
+        c_string_literal, json_string, rust_string_literal, shell_single_quote, url_percent_encode,
+        xml_attribute, yaml_scalar,
+    };
+    use crate::gen_engine::gen_engine::{
+        Clock, GenerationError, LengthDistribution, NANOID_ALPHABET, NANOID_SIZE, PositionRule,
+        Positions, SystemClock, fill_bytes, generate_bytes,
+    };
+    use crate::grouping::grouping::{format_groups, parse_groups};
+    use crate::input_policy::input_policy::InputPolicy;
+    use crate::keyboard::keyboard::{KeyboardLayout, find_keyboard_walks};
+    use crate::recovery::recovery::{
+        RECOVERY_CODE_ALPHABET, RecoveryCodeFormat, find_recovery_code, verify_recovery_code,
+    };
+    use crate::strength::strength::{NOT_WEAK_MIN_SCORE, PatternKind, Suggestion};
+    use crate::token::token::{TOKEN_ENCODINGS, TokenEncoding};
 
     #[test]
     fn it_works() {
@@ -56,17 +75,17 @@ pub mod tests {
         // Test better password (but still contains "123")
         generator.set_password("Password123");
         let score = generator.password_strength_score();
-        assert!(score >= 30 && score <= 60);
+        assert!((30..=60).contains(&score));
 
         // Test good password with special char
         generator.set_password("P@ssw0rd123");
         let score = generator.password_strength_score();
-        assert!(score >= 40 && score <= 70);
+        assert!((40..=70).contains(&score));
 
-        // Test strong password
+        // Test strong password (but still contains leetspeak "password")
         generator.set_password("MyV3ry$tr0ngP@ssw0rd!");
         let score = generator.password_strength_score();
-        assert!(score >= 70 && score <= 90);
+        assert!((55..=75).contains(&score));
     }
 
     #[test]
@@ -86,7 +105,7 @@ pub mod tests {
 
         generator.set_password("P@ssw0rd123!");
         let level = generator.password_strength_level();
-        assert!(level == "Strong" || level == "Fair");
+        assert!(level == "Fair" || level == "Weak");
 
         generator.set_password("V3ry$tr0ng&P@ssw0rdW1thEntropy!");
         assert_eq!(generator.password_strength_level(), "Strong");
@@ -173,22 +192,22 @@ pub mod tests {
         // Moderate passwords (not exact matches, but contain patterns)
         generator.set_password("Password1");
         let score3 = generator.password_strength_score();
-        assert!(score3 >= 30 && score3 <= 60);
+        assert!((30..=60).contains(&score3));
 
         // Good passwords
         generator.set_password("P@ssw0rd123");
         let score4 = generator.password_strength_score();
-        assert!(score4 >= 40 && score4 <= 70);
+        assert!((40..=70).contains(&score4));
 
-        // Strong passwords
+        // Fair passwords (leetspeak "password" and a year)
         generator.set_password("MyP@ssw0rd!2024");
         let score5 = generator.password_strength_score();
-        assert!(score5 >= 60 && score5 <= 85);
+        assert!((40..=65).contains(&score5));
 
-        // Very strong passwords
+        // Strong passwords (but still contain leetspeak "password")
         generator.set_password("V3ry$3cur3&P@ssw0rd!L0ng");
         let score6 = generator.password_strength_score();
-        assert!(score6 >= 70 && score6 <= 95);
+        assert!((55..=80).contains(&score6));
     }
 
     #[test]
@@ -217,7 +236,7 @@ pub mod tests {
         println!("Ideal password: {}", ideal_password);
 
         // This should be one of the highest possible scores
-        assert!(score >= 70 && score <= 80);
+        assert!((70..=80).contains(&score));
 
         // Let's also test with a very long random password
         let long_password = "aB1@cD2#eF3$gH4%iJ5^kL6&mN7*oP8(qR9)sT0_uV1+wX2-yZ3";
@@ -228,7 +247,7 @@ pub mod tests {
         println!("Very long password: {}", long_password);
 
         // The score might be similar or slightly higher
-        assert!(long_score >= 70 && long_score <= 80);
+        assert!((70..=80).contains(&long_score));
 
         // Let's see what our generator produces
         generator.generate(24);
@@ -241,4 +260,1496 @@ pub mod tests {
         // Generated passwords should also be strong
         assert!(generated_score >= 60);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn strength_report_breakdown() {
+        let mut generator = Passgen::default();
+
+        generator.set_password("Password123");
+        let report = generator.password_strength_report();
+        assert_eq!(report.score, generator.password_strength_score());
+        assert_eq!(report.level, generator.password_strength_level());
+        assert_eq!(report.length, 11);
+        assert_eq!(report.length_score, 20);
+        assert!(report.has_lowercase && report.has_uppercase && report.has_digits);
+        assert!(!report.has_special);
+
+        // Every component adds up to the final score
+        let sum = report.length_score + report.variety_score + report.uniqueness_score
+            - report.penalty
+            + report.entropy_score;
+        assert_eq!(sum.clamp(0, 100) as u8, report.score);
+
+        let matched: Vec<(&PatternKind, &str)> = report
+            .patterns
+            .iter()
+            .map(|p| (&p.kind, p.matched.as_str()))
+            .collect();
+        assert!(matched.contains(&(&PatternKind::WeakPattern, "password")));
+        assert!(matched.contains(&(&PatternKind::WeakPattern, "123")));
+        assert!(matched.contains(&(&PatternKind::Sequence, "123")));
+
+        generator.set_password("aaaa");
+        let report = generator.password_strength_report();
+        assert!(report.has_pattern(&PatternKind::Repeat));
+        assert!(report.has_pattern(&PatternKind::FewCharTypes));
+
+        generator.set_password("Qwerty");
+        let report = generator.password_strength_report();
+        assert!(report.has_pattern(&PatternKind::CommonPassword));
+        assert_eq!(report.score, 0);
+    }
+
+    #[test]
+    fn localized_suggestions() {
+        use crate::Language;
+
+        let mut generator = Passgen::default();
+        generator.set_password("abcdefg");
+        let suggestions = generator.password_strength_report().suggestions;
+        assert_eq!(
+            suggestions,
+            vec![
+                Suggestion::UseLongerPassword,
+                Suggestion::AddUppercase,
+                Suggestion::AddDigit,
+                Suggestion::AddSymbol,
+                Suggestion::AvoidSequences,
+            ]
+        );
+        assert_eq!(
+            generator.password_suggestions()[0],
+            "Use at least 12 characters"
+        );
+
+        generator.set_password("Password1111!");
+        let suggestions = generator.password_strength_report().suggestions;
+        assert_eq!(
+            suggestions,
+            vec![Suggestion::AvoidCommonWords, Suggestion::AvoidRepeats]
+        );
+
+        generator.set_language(Language::Russian);
+        assert_eq!(
+            generator.password_suggestions(),
+            vec![
+                "Избегайте распространённых паролей и слов вроде 'password'",
+                "Избегайте повторяющихся символов вроде 'aaa'",
+            ]
+        );
+
+        generator.set_password("Kp@3#mN9!qZ$7%vR");
+        assert!(generator.password_suggestions().is_empty());
+    }
+
+    #[test]
+    fn unicode_length_and_char_types() {
+        let mut generator = Passgen::default();
+
+        // 4 Cyrillic characters are 8 bytes, but still 4 characters
+        generator.set_password("Пёс1");
+        let report = generator.password_strength_report();
+        assert_eq!(report.length, 4);
+        assert_eq!(report.length_score, 0);
+        assert!(report.has_lowercase && report.has_uppercase && report.has_digits);
+
+        generator.set_password("Пароль!Мой");
+        let report = generator.password_strength_report();
+        assert_eq!(report.length, 10);
+        assert!(report.has_lowercase && report.has_uppercase && report.has_special);
+        assert_eq!(report.char_type_count, 3);
+
+        // Letters of a script without case
+        generator.set_password("密码很安全");
+        let report = generator.password_strength_report();
+        assert!(report.has_caseless_letters);
+        assert_eq!(report.char_type_count, 1);
+        assert!(!report.suggestions.contains(&Suggestion::AddUppercase));
+        assert!(!report.suggestions.contains(&Suggestion::AddLowercase));
+
+        // Emoji are special characters
+        generator.set_password("🙂🙃");
+        assert!(generator.password_strength_report().has_special);
+
+        // Weak patterns are compared after case folding
+        generator.set_password("PASSWORD");
+        assert_eq!(generator.password_strength_score(), 0);
+        assert_eq!(
+            crate::strength::strength::fold_case("Straße ΣΟΦΟΣ"),
+            "strasse σοφοσ"
+        );
+    }
+
+    #[test]
+    fn input_policy_normalization_and_allowed_chars() {
+        let mut generator = Passgen::default();
+
+        // Raw password is kept by default
+        generator.set_password("ｐａｓｓｗｏｒｄ");
+        assert_eq!(generator.get_password(), "ｐａｓｓｗｏｒｄ");
+
+        // NFKC is applied to the current and the following passwords
+        generator.set_input_policy(InputPolicy::nist());
+        assert_eq!(generator.get_password(), "password");
+        assert_eq!(generator.password_strength_score(), 0);
+        generator.set_password("Cafe\u{301}1!");
+        assert_eq!(generator.get_password(), "Café1!");
+
+        // Not allowed characters fail the validation
+        generator.set_password("Secret 1!");
+        assert!(generator.validate_password());
+        generator.set_password("Secret\t1!");
+        assert!(!generator.validate_password());
+        generator.set_password("Secret\u{E000}1!");
+        assert!(!generator.validate_password());
+        generator.set_password("Secret\u{0378}1!");
+        assert!(!generator.validate_password());
+
+        generator.set_input_policy(InputPolicy {
+            allow_whitespace: false,
+            ..InputPolicy::nist()
+        });
+        generator.set_password("Secret 1!");
+        assert!(!generator.validate_password());
+
+        // Zero-width characters don't add to the strength
+        generator.set_password("ab\u{200D}c\u{200D}d");
+        let report = generator.password_strength_report();
+        assert_eq!(report.length, 4);
+        assert_eq!(report.unique_chars, 4);
+    }
+
+    #[test]
+    fn descending_sequences_and_keyboard_walks() {
+        let mut generator = Passgen::default();
+
+        generator.set_password("Xcba#987");
+        let report = generator.password_strength_report();
+        assert!(report.has_pattern(&PatternKind::DescendingSequence));
+        assert!(report.suggestions.contains(&Suggestion::AvoidSequences));
+
+        assert_eq!(
+            find_keyboard_walks("1qaz2wsx"),
+            vec![
+                (KeyboardLayout::Qwerty, "1qaz".to_string()),
+                (KeyboardLayout::Qwerty, "2wsx".to_string())
+            ]
+        );
+        assert_eq!(
+            find_keyboard_walks("zxcvbn"),
+            vec![(KeyboardLayout::Qwerty, "zxcvbn".to_string())]
+        );
+        assert_eq!(
+            find_keyboard_walks("Qwertz!"),
+            vec![(KeyboardLayout::Qwertz, "Qwertz".to_string())]
+        );
+        assert_eq!(
+            find_keyboard_walks("azerty"),
+            vec![(KeyboardLayout::Azerty, "azerty".to_string())]
+        );
+        assert_eq!(
+            find_keyboard_walks("ЙЦУКЕН12"),
+            vec![(KeyboardLayout::Jcuken, "ЙЦУКЕН".to_string())]
+        );
+        assert!(find_keyboard_walks("Kp@3#mN9!qZ$7%vR").is_empty());
+
+        // Shifted walks are detected too
+        generator.set_password("!QAZ2wsx");
+        let report = generator.password_strength_report();
+        assert!(report.has_pattern(&PatternKind::KeyboardWalk(KeyboardLayout::Qwerty)));
+        assert!(
+            report
+                .suggestions
+                .contains(&Suggestion::AvoidKeyboardPatterns)
+        );
+        let level = generator.password_strength_level();
+        assert!(level == "Weak" || level == "Very Weak");
+
+        generator.set_password("1qaz!QAZ2wsx@WSX");
+        assert!(generator.password_strength_score() <= 60);
+    }
+
+    #[test]
+    fn wrong_keyboard_layout_words() {
+        use crate::Language;
+        use crate::strength::strength::find_wrong_layout_words;
+
+        assert_eq!(
+            find_wrong_layout_words("gfhjkm", &[]),
+            vec![(Language::Russian, "gfhjkm".to_string())]
+        );
+        assert_eq!(
+            find_wrong_layout_words("Ghbdtn2024!", &[]),
+            vec![(Language::Russian, "Ghbdtn".to_string())]
+        );
+        assert_eq!(
+            find_wrong_layout_words("зфыыцщкв", &[]),
+            vec![(Language::English, "зфыыцщкв".to_string())]
+        );
+        // Words typed on their own layout are not wrong layout words
+        assert!(find_wrong_layout_words("пароль", &[]).is_empty());
+        assert!(find_wrong_layout_words("password", &[]).is_empty());
+
+        let mut generator = Passgen::default();
+        generator.set_password("Gfhjkm#Rjnbr9");
+        let before = generator.password_strength_report();
+        assert!(before.has_pattern(&PatternKind::WrongLayout(Language::Russian)));
+        assert!(before.suggestions.contains(&Suggestion::AvoidCommonWords));
+
+        generator.set_password("Hnxfqs#Wmbqd9");
+        assert!(before.score < generator.password_strength_score());
+    }
+
+    #[cfg(feature = "dict-all")]
+    #[test]
+    fn wrong_keyboard_layout_dictionary_words() {
+        use crate::Language;
+        use crate::dictionary::dictionary::DictionaryScope;
+        use crate::strength::strength::find_wrong_layout_words;
+
+        // German word typed on QWERTY instead of QWERTZ, French word on QWERTY instead of AZERTY
+        assert_eq!(
+            find_wrong_layout_words("schaty", &[Language::German]),
+            vec![(Language::German, "schaty".to_string())]
+        );
+        assert_eq!(
+            find_wrong_layout_words("q;our", &[Language::French]),
+            vec![(Language::French, "q;our".to_string())]
+        );
+        // Dictionary words are matched only for the consulted languages
+        assert!(find_wrong_layout_words("schaty", &[Language::French]).is_empty());
+
+        let mut generator = Passgen::default();
+        generator.set_password("Schaty#Tulp9");
+        assert!(
+            !generator
+                .password_strength_report()
+                .has_pattern(&PatternKind::WrongLayout(Language::German))
+        );
+        generator.set_dictionary_scope(DictionaryScope::AllEnabled);
+        assert!(
+            generator
+                .password_strength_report()
+                .has_pattern(&PatternKind::WrongLayout(Language::German))
+        );
+    }
+
+    #[test]
+    fn dates_and_years() {
+        use crate::strength::strength::find_dates;
+
+        assert_eq!(find_dates("Anna1987"), vec!["1987"]);
+        assert_eq!(find_dates("x12.05.1990y"), vec!["12.05.1990"]);
+        assert_eq!(find_dates("1990-05-12"), vec!["1990-05-12"]);
+        assert_eq!(find_dates("Q050190"), vec!["050190"]);
+        assert_eq!(find_dates("19900512!"), vec!["19900512"]);
+        assert_eq!(find_dates("summer2024winter1999"), vec!["2024", "1999"]);
+        assert!(find_dates("123456").is_empty());
+        assert!(find_dates("Kp@3#mN9!qZ$7%vR").is_empty());
+
+        // The more of the password a date covers the more penalty
+        let mut generator = Passgen::default();
+        generator.set_password("Zebra#Tulip2024");
+        let short_cover = generator.password_strength_report();
+        generator.set_password("12.05.1990");
+        let full_cover = generator.password_strength_report();
+        let date_penalty = |report: &crate::strength::strength::StrengthReport| {
+            report
+                .patterns
+                .iter()
+                .find(|p| p.kind == PatternKind::Date)
+                .map(|p| p.penalty)
+                .unwrap()
+        };
+        assert!(date_penalty(&short_cover) < date_penalty(&full_cover));
+        assert_eq!(date_penalty(&full_cover), 25);
+        assert!(short_cover.suggestions.contains(&Suggestion::AvoidDates));
+    }
+
+    #[test]
+    fn scoring_of_long_passwords() {
+        use crate::strength::strength::MAX_SCORED_LENGTH;
+
+        // Only the beginning of a long input is scored
+        let mut generator = Passgen::default();
+        generator.set_password(&"p@ssw0rd!Qaz2wsx_7ru57n01".repeat(200));
+        let report = generator.password_strength_report();
+        assert_eq!(report.length, MAX_SCORED_LENGTH);
+        assert!(report.has_pattern(&PatternKind::LeetPattern));
+
+        let head: String = generator
+            .get_password()
+            .chars()
+            .take(MAX_SCORED_LENGTH)
+            .collect();
+        let score = generator.password_strength_score();
+        generator.set_password(&head);
+        assert_eq!(generator.password_strength_score(), score);
+    }
+
+    #[test]
+    fn leetspeak_dictionary_matching() {
+        use crate::strength::strength::deleet_candidates;
+
+        assert_eq!(deleet_candidates("abc"), vec!["abc"]);
+        assert_eq!(deleet_candidates("p@ss1"), vec!["p@ss1", "passi", "passl"]);
+
+        let mut generator = Passgen::default();
+
+        // Leet variants of weak passwords are almost as weak as the originals
+        generator.set_password("P@$$w0rd");
+        let report = generator.password_strength_report();
+        assert!(report.has_pattern(&PatternKind::LeetCommonPassword));
+        assert!(report.score <= 10);
+        assert_eq!(generator.password_strength_level(), "Very Weak");
+
+        generator.set_password("4dm1n");
+        assert!(generator.password_strength_score() <= 10);
+
+        // Leet variants of weak patterns are penalized
+        generator.set_password("P@ssw0rd123");
+        let report = generator.password_strength_report();
+        let leet: Vec<&str> = report
+            .patterns
+            .iter()
+            .filter(|p| p.kind == PatternKind::LeetPattern)
+            .map(|p| p.matched.as_str())
+            .collect();
+        assert_eq!(leet, vec!["password"]);
+
+        generator.set_password("L3tm31n&Zebra");
+        assert!(
+            generator
+                .password_strength_report()
+                .has_pattern(&PatternKind::LeetPattern)
+        );
+    }
+
+    #[test]
+    fn custom_blocklist() {
+        let mut generator = Passgen::default();
+        generator.set_password("Acm3#Tulip9!");
+        let before = generator.password_strength_score();
+
+        generator.add_blocklist_words(&["ACME", " Springfield ", ""]);
+        assert_eq!(generator.blocklist, vec!["acme", "springfield"]);
+
+        // Case and leetspeak variants are matched
+        let report = generator.password_strength_report();
+        assert!(report.has_pattern(&PatternKind::Blocklisted));
+        assert!(report.score < before);
+        assert!(report.suggestions.contains(&Suggestion::AvoidCommonWords));
+        assert_eq!(
+            generator.find_blocklist_words("$pr1ngf1eld"),
+            vec!["springfield"]
+        );
+
+        // Validation fails only if enabled
+        assert!(generator.validate_password());
+        generator.set_blocklist_validation(true);
+        assert!(!generator.validate_password());
+        generator.set_password("Zebra#Tulip9!");
+        assert!(generator.validate_password());
+
+        // Words can be loaded from a file
+        let path = std::env::temp_dir().join("passgen_blocklist_test.txt");
+        std::fs::write(&path, "# teams\nLakers\n\nyankees\n").unwrap();
+        generator.load_blocklist_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            generator.blocklist,
+            vec!["acme", "springfield", "lakers", "yankees"]
+        );
+        generator.set_password("GoL@kers#2");
+        assert!(!generator.validate_password());
+
+        assert!(
+            generator
+                .load_blocklist_file(std::env::temp_dir().join("no_such_blocklist.txt"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn blocklist_words_with_digits() {
+        let mut generator = Passgen::default();
+        generator
+            .add_blocklist_words(&["acme2024"])
+            .set_blocklist_validation(true);
+
+        // Digits of the word stay digits while the letters are substituted
+        for password in ["acme2024#Q", "@cme2024#Q", "@cm32024#Q", "4CME2024#Q"] {
+            generator.set_password(password);
+            assert!(!generator.validate_password(), "{password}");
+        }
+        generator.set_password("acme2025#Q");
+        assert!(generator.validate_password());
+        assert!(generator.find_blocklist_words("@cme-2024").is_empty());
+    }
+
+    #[test]
+    fn per_language_dictionaries() {
+        use crate::Language;
+        use crate::dictionary::dictionary::{
+            DictionaryScope, dictionary, enabled_languages, words,
+        };
+        use crate::lang::lang::LANGUAGES;
+
+        for language in LANGUAGES {
+            let enabled = dictionary(language).is_some();
+            assert_eq!(enabled, enabled_languages().contains(&language));
+            assert_eq!(enabled, words(language).next().is_some());
+            assert!(words(language).all(|word| !word.starts_with('#')));
+        }
+
+        let mut generator = Passgen::default();
+        generator.set_language(Language::German);
+        generator.set_password("Hallo#Welt12");
+        generator.set_dictionary_scope(DictionaryScope::Disabled);
+        let report = generator.password_strength_report();
+        assert!(!report.has_pattern(&PatternKind::DictionaryWord(Language::German)));
+    }
+
+    #[cfg(feature = "dict-all")]
+    #[test]
+    fn partly_leeted_dictionary_words() {
+        use crate::Language;
+        use crate::dictionary::dictionary::DictionaryScope;
+        use crate::strength::strength::{contains_leet_word, is_leet_word};
+
+        // Digits of the words stay digits while the letters are substituted
+        assert!(is_leet_word("7ru57n01", "trustno1"));
+        assert!(contains_leet_word("x_a1n11314", "aini1314"));
+        assert!(!is_leet_word("x_a1n11314", "aini1314"));
+
+        let mut generator = Passgen::default();
+        generator.set_dictionary_scope(DictionaryScope::AllEnabled);
+        for (password, language, word) in [
+            ("7ru57n01", Language::English, "trustno1"),
+            ("a1n11314", Language::Chinese, "aini1314"),
+            ("h4llo123", Language::German, "hallo"),
+        ] {
+            generator.set_password(password);
+            let report = generator.password_strength_report();
+            assert!(
+                report
+                    .patterns
+                    .iter()
+                    .any(|p| p.kind == PatternKind::DictionaryWord(language) && p.matched == word),
+                "{password}"
+            );
+        }
+    }
+
+    #[cfg(feature = "dict-german")]
+    #[test]
+    fn german_dictionary() {
+        use crate::Language;
+        use crate::dictionary::dictionary::DictionaryScope;
+
+        let mut generator = Passgen::default();
+        generator.set_password("H@llo#Welt12");
+        let english = generator.password_strength_score();
+
+        generator.set_language(Language::German);
+        let report = generator.password_strength_report();
+        assert!(report.has_pattern(&PatternKind::DictionaryWord(Language::German)));
+        assert!(report.score < english);
+
+        // The whole password being a dictionary word gets more penalty
+        generator.set_password("Passwort");
+        let report = generator.password_strength_report();
+        let penalty = report
+            .patterns
+            .iter()
+            .find(|p| p.matched == "passwort")
+            .unwrap()
+            .penalty;
+        assert_eq!(penalty, 30);
+
+        generator.set_language(Language::English);
+        generator.set_dictionary_scope(DictionaryScope::AllEnabled);
+        let report = generator.password_strength_report();
+        assert!(report.has_pattern(&PatternKind::DictionaryWord(Language::German)));
+    }
+
+    #[cfg(feature = "dict-all")]
+    #[test]
+    fn transliterated_dictionary_words() {
+        use crate::Language;
+        use crate::dictionary::dictionary::DictionaryScope;
+
+        let mut generator = Passgen::default();
+        generator.set_dictionary_scope(DictionaryScope::AllEnabled);
+
+        generator.set_password("Privet!2000x");
+        let report = generator.password_strength_report();
+        assert!(report.has_pattern(&PatternKind::DictionaryWord(Language::Russian)));
+
+        generator.set_password("Woaini#5201314");
+        let report = generator.password_strength_report();
+        let words: Vec<&str> = report
+            .patterns
+            .iter()
+            .filter(|p| p.kind == PatternKind::DictionaryWord(Language::Chinese))
+            .map(|p| p.matched.as_str())
+            .collect();
+        assert_eq!(words, vec!["woaini", "5201314"]);
+    }
+
+    #[test]
+    fn generator_entropy() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+        assert_eq!(Passgen::new().entropy_bits(12), Some(0.0));
+
+        // Single charset, no rejection
+        let mut generator = Passgen::new();
+        generator.set_enabled_letters(true);
+        assert!(close(
+            generator.entropy_bits(10).unwrap(),
+            10.0 * 26f64.log2()
+        ));
+        // Minimal length is 4 like in `generate`
+        assert!(close(
+            generator.entropy_bits(1).unwrap(),
+            4.0 * 26f64.log2()
+        ));
+
+        // Results without a letter or a digit are rejected
+        let mut generator = Passgen::new();
+        generator
+            .set_enabled_letters(true)
+            .set_enabled_numbers(true);
+        let accepted = 36f64.powi(4) - 26f64.powi(4) - 10f64.powi(4);
+        assert!(close(generator.entropy_bits(4).unwrap(), accepted.log2()));
+
+        // Reduced first and last positions of the "Strong & usability" rule:
+        // first is a lowercase or capital letter, the body of 2 characters has
+        // 23 lowercase, 23 capital letters and 8 digits, the last is one of 7 symbols.
+        let mut accepted: f64 = 0.0;
+        for first in 0..2 {
+            for body_1 in 0..3 {
+                for body_2 in 0..3 {
+                    let types = [first, body_1, body_2];
+                    if (0..3).all(|t| types.contains(&t)) {
+                        let weight = |t: usize| if t == 2 { 8.0 } else { 23.0 };
+                        accepted += 23.0 * weight(body_1) * weight(body_2);
+                    }
+                }
+            }
+        }
+        let generator = Passgen::default_strong_and_usab();
+        assert!(close(
+            generator.entropy_bits(4).unwrap(),
+            (accepted * 7.0).log2()
+        ));
+
+        // Repeated characters of a custom charset are less likely
+        let mut generator = Passgen::new();
+        generator.set_custom_charset("aab");
+        let per_char = -(2.0 / 3.0 * (2.0f64 / 3.0).log2() + 1.0 / 3.0 * (1.0f64 / 3.0).log2());
+        assert!(close(generator.entropy_bits(4).unwrap(), 4.0 * per_char));
+
+        let mut generator = Passgen::default();
+        let result = generator.generate_with_metadata(16);
+        assert_eq!(result.password, generator.get_password());
+        assert_eq!(result.length, 16);
+        assert_eq!(result.entropy_bits, generator.entropy_bits(16));
+        assert!(result.entropy_bits.unwrap() > 16.0 * 6.0);
+    }
+
+    #[test]
+    fn generate_by_target_entropy() {
+        let mut generator = Passgen::new();
+        generator.set_enabled_numbers(true);
+        assert_eq!(generator.length_for_entropy(128.0), Some(39));
+        assert_eq!(generator.generate_with_entropy(128.0).unwrap().len(), 39);
+
+        generator
+            .set_enabled_letters(true)
+            .set_enabled_uppercase_letters(true);
+        let length = generator.length_for_entropy(128.0).unwrap();
+        assert_eq!(length, 22);
+        assert!(generator.entropy_bits(length).unwrap() >= 128.0);
+        assert!(generator.entropy_bits(length - 1).unwrap() < 128.0);
+
+        // Minimal length is 4
+        assert_eq!(generator.length_for_entropy(1.0), Some(4));
+
+        let mut generator = Passgen::default_strong_and_usab();
+        let password = generator.generate_with_entropy(80.0).unwrap();
+        let length = password.chars().count() as u32;
+        assert!(generator.entropy_bits(length).unwrap() >= 80.0);
+        assert!(generator.entropy_bits(length - 1).unwrap() < 80.0);
+
+        // Unreachable entropy
+        let mut generator = Passgen::new();
+        generator.set_custom_charset("aaaa");
+        assert_eq!(generator.length_for_entropy(10.0), None);
+        assert_eq!(
+            generator.generate_with_entropy(10.0),
+            Err(GenerationError::EntropyNotReached)
+        );
+        assert_eq!(generator.get_password(), "");
+        assert_eq!(
+            Passgen::new().generate_with_entropy(10.0),
+            Err(GenerationError::CleanRuleset)
+        );
+
+        // The entropy is unknown, so the length reaching it is unknown too
+        let mut generator = Passgen::default();
+        generator.set_enabled_no_sequences(true);
+        assert_eq!(generator.length_for_entropy(80.0), None);
+        assert_eq!(
+            generator.generate_with_entropy(80.0),
+            Err(GenerationError::EntropyUnknown)
+        );
+        assert_eq!(generator.generate_with_metadata(16).entropy_bits, None);
+        assert_eq!(generator.entropy_bits_range(12, 16), None);
+    }
+
+    #[test]
+    fn entropy_of_long_results() {
+        // Positions are grouped by their charsets instead of being enumerated
+        let generator = Passgen::default();
+        let length = generator.length_for_entropy(1e6).unwrap();
+        assert!(generator.entropy_bits(length).unwrap() >= 1e6);
+        assert!(generator.entropy_bits(length - 1).unwrap() < 1e6);
+
+        let mut generator = Passgen::new();
+        generator.set_enabled_numbers(true).add_position_constraint(
+            Positions::Every { start: 1, step: 3 },
+            PositionRule::Only("01"),
+        );
+        let expected = 3.0 + 7.0 * 10f64.log2();
+        assert!((generator.entropy_bits(10).unwrap() - expected).abs() < 1e-9);
+        let expected = 333_333.0 + 666_667.0 * 10f64.log2();
+        assert!((generator.entropy_bits(1_000_000).unwrap() - expected).abs() < 1e-3);
+
+        // The target must be a positive number
+        for bits in [f64::NAN, f64::INFINITY, 0.0, -10.0] {
+            assert_eq!(generator.length_for_entropy(bits), None);
+        }
+    }
+
+    #[test]
+    fn generate_in_length_range() {
+        let mut generator = Passgen::default();
+        for _ in 0..50 {
+            let length = generator.generate_range(8, 12).chars().count();
+            assert!((8..=12).contains(&length));
+        }
+        assert!(generator.validate_password());
+
+        // Minimal length is 4 and the range is never empty
+        assert_eq!(generator.generate_range(1, 2).chars().count(), 4);
+        assert_eq!(generator.generate_range(10, 6).chars().count(), 10);
+        assert_eq!(
+            generator.entropy_bits_range(10, 10),
+            generator.entropy_bits(10)
+        );
+
+        // Uniform length adds its own entropy to the average entropy of the lengths
+        let mut generator = Passgen::new();
+        generator.set_enabled_numbers(true);
+        let expected: f64 = (8..=11)
+            .map(|l| generator.entropy_bits(l).unwrap())
+            .sum::<f64>()
+            / 4.0
+            + 2.0;
+        assert!((generator.entropy_bits_range(8, 11).unwrap() - expected).abs() < 1e-9);
+
+        // Keyspace weighting makes every result equally likely
+        generator.set_length_distribution(LengthDistribution::Keyspace);
+        let all: f64 = (8..=11).map(|l| 10f64.powi(l)).sum();
+        assert!((generator.entropy_bits_range(8, 11).unwrap() - all.log2()).abs() < 1e-9);
+        assert!(generator.entropy_bits_range(8, 11).unwrap() > expected);
+
+        let result = generator.generate_range_with_metadata(8, 11);
+        assert!((8..=11).contains(&result.length));
+        assert_eq!(result.entropy_bits, generator.entropy_bits_range(8, 11));
+        assert_eq!(Passgen::new().generate_range(8, 11), "");
+    }
+
+    #[test]
+    fn minimum_strength_guarantee() {
+        let mut generator = Passgen::default();
+        generator.set_min_strength_score(60);
+        for _ in 0..20 {
+            let password = generator.generate(8);
+            assert_eq!(password, generator.get_password());
+            assert!(generator.password_strength_score() >= 60);
+        }
+
+        generator.set_min_strength_score(NOT_WEAK_MIN_SCORE);
+        generator.generate(6);
+        let level = generator.password_strength_level();
+        assert!(level != "Weak" && level != "Very Weak");
+
+        // Digits only never reach a Fair score
+        let mut generator = Passgen::new();
+        generator.set_enabled_numbers(true);
+        generator
+            .set_min_strength_score(NOT_WEAK_MIN_SCORE)
+            .set_max_attempts(50);
+        assert_eq!(
+            generator.try_generate(16),
+            Err(GenerationError::StrengthNotReached {
+                min_score: NOT_WEAK_MIN_SCORE,
+                length: 16,
+                attempts: 50,
+            })
+        );
+        assert_eq!(generator.generate(16), "");
+        assert_eq!(generator.get_password(), "");
+
+        assert_eq!(
+            Passgen::new().try_generate(8),
+            Err(GenerationError::CleanRuleset)
+        );
+    }
+
+    #[test]
+    fn repeated_and_sequential_chars() {
+        let mut generator = Passgen::new();
+        generator
+            .set_enabled_letters(true)
+            .set_enabled_unique_chars(true);
+        for _ in 0..20 {
+            let password = generator.generate(26);
+            let mut chars: Vec<char> = password.chars().collect();
+            chars.sort();
+            chars.dedup();
+            assert_eq!(chars.len(), 26);
+            assert!(generator.validate_password());
+        }
+        // Not enough letters for a result without repeated ones
+        assert_eq!(
+            generator.set_max_attempts(10).try_generate(27),
+            Err(GenerationError::RulesNotSatisfied {
+                length: 27,
+                attempts: 10,
+            })
+        );
+
+        let mut generator = Passgen::new();
+        generator
+            .set_custom_charset("ab")
+            .set_max_consecutive_chars(2);
+        for _ in 0..20 {
+            assert!(!generator.generate(30).contains("aaa"));
+            assert!(!generator.get_password().contains("bbb"));
+        }
+        generator.set_password("aabba");
+        assert!(generator.validate_password());
+        generator.set_password("abbba");
+        assert!(!generator.validate_password());
+
+        let mut generator = Passgen::new();
+        generator
+            .set_custom_charset("0123")
+            .set_enabled_no_sequences(true);
+        for _ in 0..20 {
+            generator.generate(30);
+            assert!(generator.validate_password());
+            assert!(
+                !generator
+                    .password_strength_report()
+                    .has_pattern(&PatternKind::Sequence)
+            );
+        }
+        generator.set_password("0213");
+        assert!(generator.validate_password());
+        generator.set_password("0123");
+        assert!(!generator.validate_password());
+        generator.set_password("3210");
+        assert!(!generator.validate_password());
+    }
+
+    #[test]
+    fn entropy_without_repeated_chars() {
+        // Characters are drawn without replacement: log2(26!)
+        let mut generator = Passgen::new();
+        generator
+            .set_enabled_letters(true)
+            .set_enabled_unique_chars(true);
+        let expected: f64 = (1..=26).map(|n| (n as f64).log2()).sum();
+        assert!((generator.entropy_bits(26).unwrap() - expected).abs() < 1e-9);
+        assert_eq!(generator.entropy_bits(27), Some(0.0));
+
+        // The smallest length reaching the entropy can be generated
+        let mut generator = Passgen::new();
+        generator
+            .set_enabled_numbers(true)
+            .set_enabled_unique_chars(true);
+        assert_eq!(generator.length_for_entropy(21.0), Some(9));
+        assert_eq!(generator.length_for_entropy(30.0), None);
+        assert_eq!(generator.length_for_entropy(40.0), None);
+        assert_eq!(generator.generate_with_entropy(21.0).unwrap().len(), 9);
+
+        // Results without a required charset are rejected: 4-letter arrangements
+        // of the letters and numbers minus the ones of the letters or the numbers only
+        let mut generator = Passgen::new();
+        generator
+            .set_enabled_letters(true)
+            .set_enabled_numbers(true)
+            .set_enabled_unique_chars(true);
+        let arrangements = |n: f64| n * (n - 1.0) * (n - 2.0) * (n - 3.0);
+        let expected = (arrangements(36.0) - arrangements(26.0) - arrangements(10.0)).log2();
+        assert!((generator.entropy_bits(4).unwrap() - expected).abs() < 1e-9);
+
+        // Not computed exactly
+        assert_eq!(
+            Passgen::default_strong_and_usab()
+                .set_enabled_unique_chars(true)
+                .entropy_bits(12),
+            None
+        );
+        assert_eq!(
+            Passgen::default()
+                .set_max_consecutive_chars(2)
+                .entropy_bits(12),
+            None
+        );
+        assert_eq!(
+            Passgen::default()
+                .set_enabled_no_sequences(true)
+                .entropy_bits(12),
+            None
+        );
+        assert_eq!(
+            Passgen::new()
+                .set_custom_charset("aabcd")
+                .set_enabled_unique_chars(true)
+                .entropy_bits(4),
+            None
+        );
+    }
+
+    #[test]
+    fn position_constraints() {
+        let mut generator = Passgen::default();
+        generator
+            .add_position_constraint(Positions::First, PositionRule::Deny("0123456789-#"))
+            .add_position_constraint(
+                Positions::Last,
+                PositionRule::Only("abcdefghijklmnopqrstuvwxyz"),
+            )
+            .add_position_constraint(
+                Positions::Every { start: 2, step: 4 },
+                PositionRule::Only("0123456789"),
+            );
+        for _ in 0..20 {
+            let chars: Vec<char> = generator.generate(12).chars().collect();
+            assert!(!chars[0].is_ascii_digit());
+            assert!(chars[11].is_ascii_lowercase());
+            assert!(chars[2].is_ascii_digit() && chars[6].is_ascii_digit());
+            assert!(generator.validate_password());
+        }
+
+        generator.set_password("-b1$%^2&*(3x");
+        assert!(!generator.validate_password());
+        generator.set_password("Ab1$%^2&*(3x");
+        assert!(generator.validate_password());
+        generator.set_password("Ab1$%^2&*(3X");
+        assert!(!generator.validate_password());
+
+        assert!(Positions::FromEnd(1).contains(4, 6));
+        assert!(Positions::Range { start: 2, end: 4 }.contains(3, 6));
+        assert!(!Positions::Range { start: 2, end: 4 }.contains(4, 6));
+        assert!(!Positions::Index(6).contains(6, 6));
+
+        // Constraints are taken into account by the entropy
+        let mut generator = Passgen::new();
+        generator.set_enabled_numbers(true);
+        generator.add_position_constraint(Positions::First, PositionRule::Only("12"));
+        assert!((generator.entropy_bits(5).unwrap() - (1.0 + 4.0 * 10f64.log2())).abs() < 1e-9);
+
+        // A position without characters can't be generated
+        generator.add_position_constraint(Positions::First, PositionRule::Deny("12"));
+        assert_eq!(generator.entropy_bits(5), Some(0.0));
+        assert_eq!(
+            generator.try_generate(5),
+            Err(GenerationError::EmptyPosition {
+                index: 0,
+                length: 5,
+            })
+        );
+        generator.clear_position_constraints();
+        assert_eq!(generator.generate(5).len(), 5);
+
+        // Positions drawn from other charsets are valid with a custom charset
+        let mut generator = Passgen::new();
+        generator.set_custom_charset("abc");
+        generator.add_position_constraint(Positions::Last, PositionRule::Only("!"));
+        assert!(generator.generate(8).ends_with('!'));
+        assert!(generator.validate_password());
+    }
+
+    #[test]
+    fn strong_usab_positions_validation() {
+        // The first and the last positions of a custom charset are validated as generated
+        let mut generator = Passgen::new();
+        generator
+            .set_custom_charset("0123456789")
+            .set_enabled_strong_usab(true);
+        for _ in 0..20 {
+            let chars: Vec<char> = generator.generate(10).chars().collect();
+            assert!(chars[0].is_ascii_alphabetic() && !chars[9].is_alphanumeric());
+            assert!(generator.validate_password());
+        }
+        generator.set_password("a12345678$");
+        assert!(generator.validate_password());
+        generator.set_password("112345678$");
+        assert!(!generator.validate_password());
+        generator.set_password("a123456789");
+        assert!(!generator.validate_password());
+
+        // The same rule without a custom charset
+        let mut generator = Passgen::default_strong_and_usab();
+        generator.generate(12);
+        assert!(generator.validate_password());
+        generator.set_password("Xy7kPq3mZt8$");
+        assert!(generator.validate_password());
+        generator.set_password("7ykPq3mZt8X$");
+        assert!(!generator.validate_password());
+        generator.set_password("Xy7kPq3mZt8a");
+        assert!(!generator.validate_password());
+
+        // Constraints are applied after the rule
+        generator.add_position_constraint(Positions::Last, PositionRule::Only("#"));
+        assert!(generator.generate(12).ends_with('#'));
+        assert!(generator.validate_password());
+    }
+
+    #[test]
+    fn position_constraints_with_repeated_chars() {
+        // Every distinct character of an `Only` charset is equally likely
+        let mut generator = Passgen::new();
+        generator
+            .set_custom_charset("xyz")
+            .add_position_constraint(Positions::All, PositionRule::Only("aab"));
+        assert!((generator.entropy_bits(4).unwrap() - 4.0).abs() < 1e-9);
+        for _ in 0..20 {
+            let result = generator.generate(4);
+            assert!(result.chars().all(|ch| ch == 'a' || ch == 'b'));
+        }
+
+        let mut generator = Passgen::new();
+        generator
+            .set_enabled_numbers(true)
+            .add_position_constraint(Positions::First, PositionRule::Only("aaaa"));
+        assert!((generator.entropy_bits(4).unwrap() - 3.0 * 10f64.log2()).abs() < 1e-9);
+        assert!(generator.generate(4).starts_with('a'));
+    }
+
+    #[test]
+    fn context_safe_profiles() {
+        for context in SAFE_CONTEXTS {
+            let mut generator = Passgen::default();
+            generator.set_safe_contexts(&[context]);
+            for _ in 0..20 {
+                let password = generator.generate(24);
+                assert!(context.is_safe(&password), "{:?}: {}", context, password);
+                assert!(generator.validate_password());
+            }
+            assert!(
+                generator.entropy_bits(24).unwrap() <= Passgen::default().entropy_bits(24).unwrap()
+            );
+        }
+
+        let mut generator = Passgen::default();
+        generator.set_safe_contexts(&[SafeContext::Shell, SafeContext::Yaml]);
+        generator.set_password("Abc1~)(^def");
+        assert!(generator.validate_password());
+        generator.set_password("Abc1$(def");
+        assert!(!generator.validate_password());
+        generator.set_password("Abc1:(def");
+        assert!(!generator.validate_password());
+
+        assert!(!SafeContext::Json.is_safe("ab\\c1"));
+        assert!(!SafeContext::XmlAttribute.is_safe("a&b"));
+        assert!(!SafeContext::SqlConnectionString.is_safe("a;b"));
+        assert!(!SafeContext::WindowsCmd.is_safe("a%b"));
+        assert!(!SafeContext::UrlUserinfo.is_safe("a@b"));
+        assert!(!SafeContext::Json.is_safe("a b"));
+        assert!(SafeContext::UrlUserinfo.is_safe("a!$&'()*+,;=~-._b"));
+    }
+
+    #[test]
+    fn strong_usab_with_safe_contexts() {
+        // All simple symbols of the last position need escaping in a shell or YAML
+        let mut generator = Passgen::default_strong_and_usab();
+        generator.set_safe_contexts(&[SafeContext::Shell, SafeContext::Yaml]);
+        assert_eq!(
+            generator.try_generate(12),
+            Err(GenerationError::EmptyPosition {
+                index: 11,
+                length: 12,
+            })
+        );
+        assert_eq!(generator.generate(12), "");
+        assert_eq!(generator.entropy_bits(12), Some(0.0));
+
+        // Some of them are safe in a URL userinfo
+        generator.set_safe_contexts(&[SafeContext::UrlUserinfo]);
+        for _ in 0..20 {
+            let result = generator.try_generate(12).unwrap();
+            assert!(SafeContext::UrlUserinfo.is_safe(&result));
+            assert!(generator.validate_password());
+        }
+    }
+
+    #[test]
+    fn escaping_helpers() {
+        assert_eq!(shell_single_quote("a'b$`\"\\!"), r#"'a'\''b$`"\!'"#);
+        assert_eq!(
+            json_string("a\"b\\c\n\u{1}\u{2028}🙂"),
+            r#""a\"b\\c\n\u0001\u2028🙂""#
+        );
+        assert_eq!(
+            url_percent_encode("Az09-._~ @:/п"),
+            "Az09-._~%20%40%3A%2F%D0%BF"
+        );
+        assert_eq!(
+            yaml_scalar("a\"b\\#: \t\u{7}\u{85}п"),
+            r#""a\"b\\#: \t\x07\Nп""#
+        );
+        assert_eq!(
+            xml_attribute("<a href=\"x\">&'\n").as_deref(),
+            Some("\"&lt;a href=&quot;x&quot;&gt;&amp;&apos;&#10;\"")
+        );
+        assert_eq!(xml_attribute("a\u{1}b"), None);
+        assert_eq!(
+            c_string_literal("a\"\\??/\n\u{1b}1п"),
+            r#""a\"\\\?\?/\n\0331\320\277""#
+        );
+        assert_eq!(rust_string_literal("a\"\\\n\u{1b}п"), r#""a\"\\\n\u{1b}п""#);
+
+        // Every character of the full symbol set and a custom Unicode charset survives
+        let mut generator = Passgen::default();
+        let password = generator.generate(64).to_string();
+        assert_eq!(
+            shell_single_quote(&password),
+            format!("'{}'", password.replace('\'', "'\\''"))
+        );
+        assert!(!url_percent_encode(&password).contains(['@', '#', '$', '&', '[', ']']));
+
+        generator.set_custom_charset("абв⭕🙂\"'\\");
+        let password = generator.generate(32).to_string();
+        let json = json_string(&password);
+        assert_eq!(
+            json.chars().filter(|&ch| ch == '"').count(),
+            password.matches('"').count() + 2
+        );
+        assert!(xml_attribute(&password).is_some());
+    }
+
+    #[test]
+    fn token_encodings() {
+        let vectors = [
+            (TokenEncoding::Hex, "666f6f626172"),
+            (TokenEncoding::Base32, "MZXW6YTBOI"),
+            (TokenEncoding::Crockford32, "CSQPYRK1E8"),
+            (TokenEncoding::ZBase32, "c3zs6aubqe"),
+            (TokenEncoding::Base58, "t1Zv2yaZ"),
+            (TokenEncoding::Base64Url, "Zm9vYmFy"),
+        ];
+        for (encoding, encoded) in vectors {
+            assert_eq!(encoding.encode(b"foobar"), encoded);
+            assert_eq!(encoding.decode(encoded).unwrap(), b"foobar");
+        }
+        assert_eq!(
+            TokenEncoding::Base58.encode(b"\0\0Hello World!"),
+            "112NEpo7TZRRrLZSi2U"
+        );
+        assert_eq!(TokenEncoding::Base64Url.encode(&[0xfb, 0xff]), "-_8");
+
+        // Padding, case and Crockford's lookalikes are accepted by the decoders
+        assert_eq!(
+            TokenEncoding::Base32.decode("mzxw6ytb======").unwrap(),
+            b"fooba"
+        );
+        assert_eq!(
+            TokenEncoding::Base64Url.decode("Zm9vYg==").unwrap(),
+            b"foob"
+        );
+        assert_eq!(
+            TokenEncoding::Crockford32.decode("csqp-yrkl-e8").unwrap(),
+            b"foobar"
+        );
+        assert_eq!(TokenEncoding::Hex.decode("666F6F"), Some(b"foo".to_vec()));
+        // Invalid characters and not canonical endings are rejected
+        assert_eq!(TokenEncoding::Hex.decode("66g"), None);
+        assert_eq!(TokenEncoding::Hex.decode("666"), None);
+        assert_eq!(TokenEncoding::Base64Url.decode("Zm9"), None);
+        assert_eq!(TokenEncoding::Base58.decode("0OIl"), None);
+
+        let mut generator = Passgen::new();
+        for encoding in TOKEN_ENCODINGS {
+            for bytes in [0, 1, 2, 5, 16, 32, 33] {
+                let result = generator.generate_token(bytes, encoding);
+                assert_eq!(result.entropy_bits, (bytes * 8) as f64);
+                assert_eq!(result.token, generator.get_password());
+                assert!(
+                    result
+                        .token
+                        .chars()
+                        .all(|ch| encoding.alphabet().contains(ch))
+                );
+                assert_eq!(encoding.decode(&result.token).unwrap().len(), bytes);
+            }
+        }
+        let result = generator.generate_token(32, TokenEncoding::Base64Url);
+        assert_eq!(result.token.len(), 43);
+        assert_eq!(result.description(), "256-bit token, base64url, no padding");
+        assert_eq!(
+            generator
+                .generate_token(16, TokenEncoding::Hex)
+                .description(),
+            "128-bit token, hex"
+        );
+    }
+
+    #[test]
+    fn raw_random_bytes() {
+        assert!(generate_bytes(0).is_empty());
+        let first = generate_bytes(32);
+        let second = generate_bytes(32);
+        assert_eq!(first.len(), 32);
+        assert_ne!(first, second);
+
+        let mut buffer = [0u8; 64];
+        fill_bytes(&mut buffer);
+        assert!(buffer.iter().any(|&byte| byte != 0));
+
+        // Every byte value shows up in a large enough sample
+        let sample = generate_bytes(64 * 1024);
+        let mut seen = [false; 256];
+        for byte in sample {
+            seen[byte as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn api_keys_with_checksum() {
+        assert_eq!(ChecksumAlgorithm::Crc32.checksum(b"123456789"), 0xCBF43926);
+        assert_eq!(ChecksumAlgorithm::Crc32c.checksum(b"123456789"), 0xE3069283);
+
+        let mut generator = Passgen::new();
+        let format = ApiKeyFormat::new("acme_live_");
+        let key = generator.generate_api_key(&format);
+        assert_eq!(key, generator.get_password());
+        assert_eq!(key.len(), 10 + 30 + 6);
+        assert!(key.starts_with("acme_live_"));
+        assert!(verify_api_key(&key, &format));
+        assert!((format.entropy_bits() - 30.0 * 62f64.log2()).abs() < 1e-9);
+
+        // A typo, a wrong prefix or length is rejected
+        let mut typo: Vec<char> = key.chars().collect();
+        typo[15] = if typo[15] == 'a' { 'b' } else { 'a' };
+        assert!(!verify_api_key(&String::from_iter(typo), &format));
+        assert!(!verify_api_key(&key.replace("_live_", "_test_"), &format));
+        assert!(!verify_api_key(&key[..key.len() - 1], &format));
+        assert!(!verify_api_key(
+            &format!("{}-", &key[..key.len() - 1]),
+            &format
+        ));
+
+        let format = ApiKeyFormat {
+            prefix: "ghp_".to_string(),
+            body_length: 20,
+            checksum: Some(ChecksumAlgorithm::Crc32c),
+        };
+        let key = generator.generate_api_key(&format);
+        assert_eq!(key.len(), 4 + 20 + 6);
+        assert!(verify_api_key(&key, &format));
+        assert!(!verify_api_key(&key, &ApiKeyFormat::new("ghp_")));
+
+        let format = ApiKeyFormat {
+            prefix: "sk-".to_string(),
+            body_length: 40,
+            checksum: None,
+        };
+        let key = generator.generate_api_key(&format);
+        assert_eq!(key.len(), 43);
+        assert!(verify_api_key(&key, &format));
+    }
+
+    struct FixedClock(u64);
+
+    impl Clock for FixedClock {
+        fn now_millis(&self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn identifiers() {
+        let mut generator = Passgen::new();
+        let uuid = generator.generate_uuid_v4();
+        assert_eq!(uuid, generator.get_password());
+        let parts: Vec<&str> = uuid.split('-').collect();
+        assert_eq!(
+            parts.iter().map(|p| p.len()).collect::<Vec<_>>(),
+            [8, 4, 4, 4, 12]
+        );
+        assert!(parts[2].starts_with('4'));
+        assert!(parts[3].starts_with(['8', '9', 'a', 'b']));
+        assert_ne!(uuid, generator.generate_uuid_v4());
+
+        // Time-ordered identifiers keep the time of the clock
+        let clock = FixedClock(0x0189_7F2A_3B4C);
+        let uuid = generator.generate_uuid_v7(&clock);
+        assert!(uuid.starts_with("01897f2a-3b4c-7"));
+        assert!(uuid[19..].starts_with(['8', '9', 'a', 'b']));
+        let later = generator.generate_uuid_v7(&FixedClock(0x0189_7F2A_3B4D));
+        assert!(uuid < later);
+
+        let ulid = generator.generate_ulid(&FixedClock(1469918176385));
+        assert_eq!(ulid.len(), 26);
+        assert!(ulid.starts_with("01ARYZ6S41"));
+        assert!(TokenEncoding::Crockford32.decode(&ulid[10..]).is_some());
+        assert!(ulid < generator.generate_ulid(&FixedClock(1469918176386)));
+        assert!(generator.generate_ulid(&SystemClock) > ulid);
+
+        let id = generator.generate_nanoid(NANOID_ALPHABET, NANOID_SIZE);
+        assert_eq!(id.len(), 21);
+        assert!(id.chars().all(|ch| NANOID_ALPHABET.contains(ch)));
+        let id = generator.generate_nanoid("абв", 10);
+        assert_eq!(id.chars().count(), 10);
+        assert!(id.chars().all(|ch| "абв".contains(ch)));
+        assert_eq!(generator.generate_nanoid("", 10), "");
+    }
+
+    #[test]
+    fn recovery_codes() {
+        let mut generator = Passgen::new();
+        let format = RecoveryCodeFormat::default();
+        let codes = generator.generate_recovery_codes(16, &format);
+        assert_eq!(codes.len(), 16);
+        let unique: std::collections::HashSet<&String> = codes.iter().collect();
+        assert_eq!(unique.len(), 16);
+        for code in &codes {
+            let (first, second) = code.split_once('-').unwrap();
+            assert_eq!((first.len(), second.len()), (4, 4));
+            assert!(
+                code.replace('-', "")
+                    .chars()
+                    .all(|ch| RECOVERY_CODE_ALPHABET.contains(ch))
+            );
+        }
+        assert!((format.entropy_bits() - 8.0 * 31f64.log2()).abs() < 1e-9);
+
+        // Case, dashes and spaces of the entered code are normalized
+        assert!(verify_recovery_code("K7FQ 9XMD", "k7fq-9xmd", &format));
+        assert!(verify_recovery_code(" k7fq9xmd\t", "k7fq-9xmd", &format));
+        assert!(!verify_recovery_code("k7fq-9xme", "k7fq-9xmd", &format));
+        assert!(!verify_recovery_code("k7fq-9xm", "k7fq-9xmd", &format));
+        let entered = codes[5].to_uppercase();
+        assert_eq!(find_recovery_code(&entered, &codes, &format), Some(5));
+        assert_eq!(find_recovery_code("zzzz-zzzz", &codes[..0], &format), None);
+
+        // Lookalikes are read as the characters of the alphabet
+        let format = RecoveryCodeFormat {
+            alphabet: "0123456789",
+            groups: 3,
+            group_size: 3,
+            separator: " ",
+        };
+        assert!(verify_recovery_code("1O2-l34-5i6", "102 134 516", &format));
+        let codes = generator.generate_recovery_codes(5, &format);
+        assert!(
+            codes
+                .iter()
+                .all(|code| code.len() == 11 && code.matches(' ').count() == 2)
+        );
+
+        // No more codes than the format can hold
+        let format = RecoveryCodeFormat {
+            alphabet: "ab",
+            groups: 1,
+            group_size: 2,
+            separator: "-",
+        };
+        let mut codes = generator.generate_recovery_codes(10, &format);
+        codes.sort();
+        assert_eq!(codes, ["aa", "ab", "ba", "bb"]);
+    }
+
+    #[test]
+    fn grouped_output() {
+        assert_eq!(format_groups("abcdefghijkl", 4, " "), "abcd efgh ijkl");
+        assert_eq!(format_groups("abcdefghij", 4, "-"), "abcd-efgh-ij");
+        assert_eq!(format_groups("абвгд", 2, "·"), "аб·вг·д");
+        assert_eq!(format_groups("abc", 0, "-"), "abc");
+        assert_eq!(parse_groups("abcd-efgh-ij", 4, "-").unwrap(), "abcdefghij");
+        // Separators inside the groups belong to the result
+        assert_eq!(parse_groups("a-cd-ef-h", 4, "-").unwrap(), "a-cdef-h");
+        assert_eq!(parse_groups("abcd-efgh-", 4, "-"), None);
+        assert_eq!(parse_groups("abcdefgh", 4, "-"), None);
+        assert_eq!(parse_groups("abc-defg", 4, "-"), None);
+
+        let mut generator = Passgen::default();
+        generator.set_grouping(4, "-");
+        let result = generator.generate(12);
+        assert_eq!(result, generator.get_password());
+        let groups: Vec<&str> = result.split('-').collect();
+        assert_eq!(groups.len(), 3);
+        assert!(generator.validate_password());
+        assert_eq!(generator.ungrouped_password().chars().count(), 12);
+        // The separators don't count to the entropy
+        let result = generator.generate_with_metadata(12);
+        assert_eq!(result.length, 12);
+        assert_eq!(result.entropy_bits, Passgen::default().entropy_bits(12));
+
+        // The validation checks the characters without the separators
+        let mut generator = Passgen::new();
+        generator.set_custom_charset("ABCD").set_grouping(3, " ");
+        for _ in 0..10 {
+            assert_eq!(generator.generate(9).len(), 11);
+            assert!(generator.validate_password());
+        }
+        generator.set_password("ABC DAB CDA");
+        assert!(generator.validate_password());
+        generator.set_password("ABCDABCDA");
+        assert!(generator.validate_password());
+        generator.set_password("ABC-DAB-CDA");
+        assert!(!generator.validate_password());
+    }
+
+    #[test]
+    fn grouped_output_scoring_and_contexts() {
+        // The separators don't count to the strength score
+        let mut generator = Passgen::new();
+        generator
+            .set_enabled_uppercase_letters(true)
+            .set_enabled_numbers(true);
+        generator.set_grouping(4, "-").set_min_strength_score(50);
+        for _ in 0..10 {
+            let result = generator.generate(12);
+            let report = generator.password_strength_report();
+            assert!(!report.has_special);
+            assert_eq!(report.length, 12);
+            generator.set_password(&result.replace('-', ""));
+            assert_eq!(generator.password_strength_report(), report);
+            generator.set_password(&result);
+            assert!(generator.password_strength_score() >= 50);
+        }
+
+        // The separator must be safe in the target contexts as well
+        generator.set_safe_contexts(&[SafeContext::Yaml]);
+        assert_eq!(
+            generator.try_generate(12),
+            Err(GenerationError::UnsafeSeparator)
+        );
+        generator.set_password("AB12-CD34-EF56");
+        assert!(!generator.validate_password());
+        generator.set_grouping(4, ".");
+        let result = generator.try_generate(12).unwrap();
+        assert!(SafeContext::Yaml.is_safe(&result));
+        assert!(generator.validate_password());
+    }
+
+    #[test]
+    fn check_digit_codes() {
+        assert_eq!(CheckAlgorithm::Luhn.check_char("7992739871"), Some('3'));
+        assert_eq!(CheckAlgorithm::Damm.check_char("572"), Some('4'));
+        assert_eq!(CheckAlgorithm::Verhoeff.check_char("236"), Some('3'));
+        assert_eq!(
+            CheckAlgorithm::Iso7064Mod37_36.check_char("A12425GABC1234002"),
+            Some('M')
+        );
+        assert!(CheckAlgorithm::Luhn.validate("79927398713"));
+        assert!(CheckAlgorithm::Iso7064Mod37_36.validate("a12425gabc1234002m"));
+        assert!(!CheckAlgorithm::Damm.validate("57a4"));
+        assert!(!CheckAlgorithm::Damm.validate(""));
+        assert_eq!(CheckAlgorithm::Verhoeff.check_char("12x"), None);
+
+        let algorithms = [
+            CheckAlgorithm::Luhn,
+            CheckAlgorithm::Damm,
+            CheckAlgorithm::Verhoeff,
+            CheckAlgorithm::Iso7064Mod37_36,
+            CheckAlgorithm::Iso7064Hybrid("ABCDEFGHJKMNPQRSTVWXYZ23456789"),
+        ];
+        let mut generator = Passgen::new();
+        for algorithm in algorithms {
+            for _ in 0..20 {
+                let code = generator.generate_check_code(12, algorithm);
+                assert_eq!(code, generator.get_password());
+                assert_eq!(code.chars().count(), 12);
+                assert!(code.chars().all(|ch| algorithm.alphabet().contains(ch)));
+                assert!(algorithm.validate(&code));
+
+                // Every single-character typo is detected
+                let chars: Vec<char> = code.chars().collect();
+                for i in 0..chars.len() {
+                    for typo in algorithm.alphabet().chars().filter(|&ch| ch != chars[i]) {
+                        let mut changed = chars.clone();
+                        changed[i] = typo;
+                        assert!(!algorithm.validate(&String::from_iter(changed)));
+                    }
+                }
+
+                // And adjacent transpositions by the numeric algorithms, except 09 and 90 by Luhn
+                if algorithm.alphabet() != "0123456789" {
+                    continue;
+                }
+                for i in 0..chars.len() - 1 {
+                    let pair = (chars[i], chars[i + 1]);
+                    if pair.0 == pair.1
+                        || (algorithm == CheckAlgorithm::Luhn
+                            && (pair == ('0', '9') || pair == ('9', '0')))
+                    {
+                        continue;
+                    }
+                    let mut swapped = chars.clone();
+                    swapped.swap(i, i + 1);
+                    assert!(!algorithm.validate(&String::from_iter(swapped)));
+                }
+            }
+        }
+        assert_eq!(
+            generator.generate_check_code(1, CheckAlgorithm::Damm).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn check_digit_hybrid_alphabets() {
+        // Every single-character typo of every 3-character payload is detected
+        let algorithm = CheckAlgorithm::Iso7064Hybrid("ABCDEFGHIJ");
+        let alphabet: Vec<char> = algorithm.alphabet().chars().collect();
+        for a in &alphabet {
+            for b in &alphabet {
+                for c in &alphabet {
+                    let payload = String::from_iter([a, b, c]);
+                    let code = format!("{}{}", payload, algorithm.check_char(&payload).unwrap());
+                    assert!(algorithm.validate(&code));
+                    let chars: Vec<char> = code.chars().collect();
+                    for i in 0..chars.len() {
+                        for &typo in alphabet.iter().filter(|&&ch| ch != chars[i]) {
+                            let mut changed = chars.clone();
+                            changed[i] = typo;
+                            assert!(!algorithm.validate(&String::from_iter(changed)));
+                        }
+                    }
+                }
+            }
+        }
+
+        // Alphabets of an odd size, with repeated characters or empty ones are not supported
+        let mut generator = Passgen::new();
+        for alphabet in ["ABCDEFGHI", "ABCDEFGHIA", ""] {
+            let algorithm = CheckAlgorithm::Iso7064Hybrid(alphabet);
+            assert_eq!(algorithm.check_char("ABC"), None);
+            assert!(!algorithm.validate("ABCD"));
+            assert!(!algorithm.validate("x"));
+            assert_eq!(generator.generate_check_code(8, algorithm), "");
+            assert_eq!(generator.get_password(), "");
+        }
+    }
+}
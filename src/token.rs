@@ -0,0 +1,261 @@
+pub mod token {
+    use crate::Passgen;
+    use crate::gen_engine::gen_engine::generate_bytes;
+
+    // Hexadecimal alphabet, lowercase.
+    pub const HEX_ALPHABET: &str = "0123456789abcdef";
+    // Base32 alphabet of RFC 4648.
+    pub const BASE32_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+    // Crockford's Base32 alphabet without I, L, O and U.
+    pub const CROCKFORD_ALPHABET: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+    // z-base-32 alphabet, ordered by how easy the characters are to read and type.
+    pub const Z_BASE32_ALPHABET: &str = "ybndrfg8ejkmcpqxot1uwisza345h769";
+    // Base58 alphabet of Bitcoin without 0, O, I and l.
+    pub const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    // URL and filename safe Base64 alphabet of RFC 4648.
+    pub const BASE64URL_ALPHABET: &str =
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+    /// Binary-to-text encodings of the random tokens.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum TokenEncoding {
+        /// Lowercase hexadecimal, 4 bits per character.
+        Hex,
+        /// Base32 of RFC 4648 without padding, 5 bits per character.
+        Base32,
+        /// Crockford's Base32, 5 bits per character.
+        /// Decoding is case insensitive, reads `O` as `0`, `I` and `L` as `1` and skips hyphens.
+        Crockford32,
+        /// z-base-32, 5 bits per character.
+        ZBase32,
+        /// Base58 of Bitcoin, leading zero bytes are encoded as `1`.
+        Base58,
+        /// URL and filename safe Base64 of RFC 4648 without padding, 6 bits per character.
+        Base64Url,
+    }
+
+    /// All supported token encodings.
+    pub const TOKEN_ENCODINGS: [TokenEncoding; 6] = [
+        TokenEncoding::Hex,
+        TokenEncoding::Base32,
+        TokenEncoding::Crockford32,
+        TokenEncoding::ZBase32,
+        TokenEncoding::Base58,
+        TokenEncoding::Base64Url,
+    ];
+
+    /// Random token with its metadata.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Token {
+        /// The encoded token.
+        pub token: String,
+        /// Encoding of the token.
+        pub encoding: TokenEncoding,
+        /// Exact entropy of the token: 8 bits per random byte.
+        pub entropy_bits: f64,
+    }
+
+    impl Token {
+        /// Describe the token, like `256-bit token, base64url, no padding`.
+        pub fn description(&self) -> String {
+            let padding = match self.encoding {
+                TokenEncoding::Base32 | TokenEncoding::Base64Url => ", no padding",
+                _ => "",
+            };
+            format!(
+                "{}-bit token, {}{}",
+                self.entropy_bits,
+                self.encoding.name(),
+                padding
+            )
+        }
+    }
+
+    impl TokenEncoding {
+        /// Name of the encoding.
+        pub fn name(&self) -> &'static str {
+            match self {
+                TokenEncoding::Hex => "hex",
+                TokenEncoding::Base32 => "base32",
+                TokenEncoding::Crockford32 => "crockford base32",
+                TokenEncoding::ZBase32 => "z-base-32",
+                TokenEncoding::Base58 => "base58",
+                TokenEncoding::Base64Url => "base64url",
+            }
+        }
+
+        /// Alphabet of the encoding.
+        pub fn alphabet(&self) -> &'static str {
+            match self {
+                TokenEncoding::Hex => HEX_ALPHABET,
+                TokenEncoding::Base32 => BASE32_ALPHABET,
+                TokenEncoding::Crockford32 => CROCKFORD_ALPHABET,
+                TokenEncoding::ZBase32 => Z_BASE32_ALPHABET,
+                TokenEncoding::Base58 => BASE58_ALPHABET,
+                TokenEncoding::Base64Url => BASE64URL_ALPHABET,
+            }
+        }
+
+        // Bits per character of the encodings working on groups of bits.
+        fn bits_per_char(&self) -> Option<u32> {
+            match self {
+                TokenEncoding::Hex => Some(4),
+                TokenEncoding::Base32 | TokenEncoding::Crockford32 | TokenEncoding::ZBase32 => {
+                    Some(5)
+                }
+                TokenEncoding::Base64Url => Some(6),
+                TokenEncoding::Base58 => None,
+            }
+        }
+
+        /// Encode the bytes.
+        pub fn encode(&self, bytes: &[u8]) -> String {
+            let alphabet: Vec<char> = self.alphabet().chars().collect();
+            match self.bits_per_char() {
+                Some(bits) => encode_bits(bytes, &alphabet, bits),
+                None => encode_base58(bytes, &alphabet),
+            }
+        }
+
+        /// Decode the text encoded by the encoding.
+        /// Padding of Base32 and Base64url is accepted too.
+        /// Returns `None` if the text is not a valid encoding of any bytes.
+        pub fn decode(&self, text: &str) -> Option<Vec<u8>> {
+            let text = match self {
+                TokenEncoding::Base32 | TokenEncoding::Base64Url => text.trim_end_matches('='),
+                _ => text,
+            };
+            let digits = text
+                .chars()
+                .filter(|&ch| !(*self == TokenEncoding::Crockford32 && ch == '-'))
+                .map(|ch| self.digit(ch))
+                .collect::<Option<Vec<u32>>>()?;
+            match self.bits_per_char() {
+                Some(bits) => decode_bits(&digits, bits),
+                None => Some(decode_base58(&digits)),
+            }
+        }
+
+        // Value of the character in the alphabet.
+        fn digit(&self, ch: char) -> Option<u32> {
+            let ch = match self {
+                TokenEncoding::Hex | TokenEncoding::ZBase32 => ch.to_ascii_lowercase(),
+                TokenEncoding::Base32 => ch.to_ascii_uppercase(),
+                TokenEncoding::Crockford32 => match ch.to_ascii_uppercase() {
+                    'O' => '0',
+                    'I' | 'L' => '1',
+                    upper => upper,
+                },
+                TokenEncoding::Base58 | TokenEncoding::Base64Url => ch,
+            };
+            self.alphabet()
+                .chars()
+                .position(|letter| letter == ch)
+                .map(|position| position as u32)
+        }
+    }
+
+    impl Passgen {
+        /// Generate a token of the random bytes encoded by the encoding.
+        /// The token is automatically stored in the `password` field.
+        ///
+        /// Unlike `generate` with a custom charset, the token keeps the bytes,
+        /// which can be decoded back by [TokenEncoding::decode].
+        pub fn generate_token(&mut self, bytes: usize, encoding: TokenEncoding) -> Token {
+            let random = generate_bytes(bytes);
+            let token = encoding.encode(&random);
+            self.password = token.clone();
+            Token {
+                token,
+                encoding,
+                entropy_bits: (bytes * 8) as f64,
+            }
+        }
+    }
+
+    // Encode the bytes by groups of bits from the most significant one,
+    // the last group is padded with zero bits.
+    fn encode_bits(bytes: &[u8], alphabet: &[char], bits: u32) -> String {
+        let mut result = String::new();
+        let mut buffer: u32 = 0;
+        let mut buffered = 0;
+        for &byte in bytes {
+            buffer = (buffer << 8) | byte as u32;
+            buffered += 8;
+            while buffered >= bits {
+                buffered -= bits;
+                result.push(alphabet[((buffer >> buffered) & ((1 << bits) - 1)) as usize]);
+            }
+            buffer &= (1 << buffered) - 1;
+        }
+        if buffered > 0 {
+            result.push(alphabet[((buffer << (bits - buffered)) & ((1 << bits) - 1)) as usize]);
+        }
+        result
+    }
+
+    // Decode the groups of bits, only the canonical encoding is accepted:
+    // the padding bits must be zero and shorter than a character.
+    fn decode_bits(digits: &[u32], bits: u32) -> Option<Vec<u8>> {
+        let mut result = Vec::new();
+        let mut buffer: u32 = 0;
+        let mut buffered = 0;
+        for &digit in digits {
+            buffer = (buffer << bits) | digit;
+            buffered += bits;
+            if buffered >= 8 {
+                buffered -= 8;
+                result.push((buffer >> buffered) as u8);
+            }
+            buffer &= (1 << buffered) - 1;
+        }
+        if buffered >= bits || buffer != 0 {
+            return None;
+        }
+        Some(result)
+    }
+
+    // Encode the bytes as a big number in base 58.
+    fn encode_base58(bytes: &[u8], alphabet: &[char]) -> String {
+        let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+        // Digits of the number from the least significant one
+        let mut digits: Vec<u32> = Vec::new();
+        for &byte in &bytes[zeros..] {
+            let mut carry = byte as u32;
+            for digit in digits.iter_mut() {
+                carry += *digit << 8;
+                *digit = carry % 58;
+                carry /= 58;
+            }
+            while carry > 0 {
+                digits.push(carry % 58);
+                carry /= 58;
+            }
+        }
+        std::iter::repeat_n(alphabet[0], zeros)
+            .chain(digits.iter().rev().map(|&digit| alphabet[digit as usize]))
+            .collect()
+    }
+
+    // Decode the big number in base 58 to bytes.
+    fn decode_base58(digits: &[u32]) -> Vec<u8> {
+        let zeros = digits.iter().take_while(|&&digit| digit == 0).count();
+        // Bytes of the number from the least significant one
+        let mut bytes: Vec<u8> = Vec::new();
+        for &digit in &digits[zeros..] {
+            let mut carry = digit;
+            for byte in bytes.iter_mut() {
+                carry += (*byte as u32) * 58;
+                *byte = carry as u8;
+                carry >>= 8;
+            }
+            while carry > 0 {
+                bytes.push(carry as u8);
+                carry >>= 8;
+            }
+        }
+        std::iter::repeat_n(0, zeros)
+            .chain(bytes.into_iter().rev())
+            .collect()
+    }
+}